@@ -0,0 +1,716 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
+
+use aoc::bench::{self, Settings};
+use aoc::gen::{self, CheckError};
+use aoc::known::{self, KnownAnswers, Verdict};
+use aoc::viz::Viewer;
+use aoc::{Day, Part, RawInput, Source, Unsolved};
+
+const USAGE: &str = "\
+Usage: aoc run --day <day> [--part <part>] [<filename> | - | --example [<name>]]
+       aoc run --all [--part <part>] [--example [<name>]]
+       aoc watch --day <day> [--delay <ms>] [--paused] [<filename> | - | --example [<name>]]
+       aoc bench (--day <day> | --all) [--part <part>] [--warmup <n>] [--iterations <n>]
+                 [--save <file>] [--baseline <file>] [--threshold <percent>] [<input>]
+       aoc verify [--day <day> | --all] [--part <part>] [--answers <file>] [--record | --overwrite]
+                  [<filename> | --example [<name>]]
+       aoc check (--day <day> | --all) [--part <part>] [--cases <n>] [--seed <n>] [--size <n>]
+
+Without a filename, the input is read from stdin when it's piped in, or from
+inputs/dayNN.txt otherwise. `--example 2` reads the day's example2.txt.
+
+While watching, type p and Enter to pause, Enter to step, + or - to change the
+speed and q to skip to the end.
+
+Benchmarks time parsing and every part separately, and report the median, min
+and max of the iterations. --save merges the results into a file, --baseline
+compares against one and fails when a median got more than --threshold percent
+(10 by default) slower.
+
+Verify checks every answer against inputs/answers.txt and fails when one
+differs. --record adds the answers that weren't known yet, --overwrite also
+replaces the ones that differ.
+
+Check compares days with an input generator against their slow reference
+solution on random inputs, and shrinks the first input they disagree on. Pass
+the seed it prints with --cases 1 to repeat that case.";
+
+fn days() -> Vec<&'static dyn Day> {
+    vec![
+        &day01_lists::Day01,
+        &day02_reports::Day02,
+        &day03_computer::Day03,
+        &day04_wordsearch::Day04,
+        &day05_safety_manual::Day05,
+        &day06_guardsguardsguards::Day06,
+        &day07_calibration::Day07,
+        &day08_antennas::Day08,
+        &day09_disk_fragmenter::Day09,
+        &day10_hoof_it::Day10,
+        &day11_pebbles::Day11,
+        &day12_gardens::Day12,
+        &day13_the_claw::Day13,
+        &day14_teleporting_robots::Day14,
+        &day15_boxes::Day15,
+        &day16_reindeer_maze::Day16,
+        &day17_computer::Day17,
+        &day18_falling::Day18,
+        &day19_towels::Day19,
+        &day20_race::Day20,
+        &day21_keypads::Day21,
+        &day22_monkey_market::Day22,
+        &day23_lan_party::Day23,
+        &day24_wires::Day24,
+    ]
+}
+
+#[derive(Debug, PartialEq)]
+enum Selection {
+    All,
+    Day(u8),
+}
+
+#[derive(Debug, PartialEq)]
+struct Run {
+    selection: Selection,
+    part: Option<Part>,
+    source: Source,
+}
+
+#[derive(Debug, PartialEq)]
+struct Watch {
+    day: u8,
+    source: Source,
+    delay: Duration,
+    paused: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct Bench {
+    run: Run,
+    settings: Settings,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+#[derive(Debug, PartialEq)]
+struct Verify {
+    run: Run,
+    answers: PathBuf,
+    record: bool,
+    overwrite: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct Check {
+    selection: Selection,
+    part: Option<Part>,
+    settings: gen::Settings,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(Run),
+    Watch(Watch),
+    Bench(Bench),
+    Verify(Verify),
+    Check(Check),
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(String::as_str) {
+        Some("run") => parse_run(&args[1..]).map(Command::Run),
+        Some("watch") => parse_watch(&args[1..]).map(Command::Watch),
+        Some("bench") => parse_bench(&args[1..]).map(Command::Bench),
+        Some("verify") => parse_verify(&args[1..]).map(Command::Verify),
+        Some("check") => parse_check(&args[1..]).map(Command::Check),
+        Some(command) => Err(format!("Unknown command: {}", command)),
+        None => Err("Missing command".to_string()),
+    }
+}
+
+fn parse_day(value: Option<&String>) -> Result<u8, String> {
+    let day = value.ok_or("Missing value for --day")?;
+    day.parse().map_err(|_| format!("Invalid day: {}", day))
+}
+
+fn parse_run(args: &[String]) -> Result<Run, String> {
+    let mut args = args.iter();
+    let mut selection = None;
+    let mut part = None;
+    let mut input_args = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => selection = Some(Selection::All),
+            "--day" => selection = Some(Selection::Day(parse_day(args.next())?)),
+            "--part" => {
+                let value = args.next().ok_or("Missing value for --part")?;
+                part = Some(Part::try_from(value.as_str())?);
+            }
+            _ => input_args.push(arg.clone()),
+        }
+    }
+
+    let selection = selection.ok_or("Pass either --day <day> or --all")?;
+    let source = Source::from_args(&input_args).map_err(|e| e.to_string())?;
+    if selection == Selection::All && matches!(source, Source::File(_) | Source::Stdin) {
+        return Err("A filename can only be passed for a single day".to_string());
+    }
+
+    Ok(Run { selection, part, source })
+}
+
+fn parse_watch(args: &[String]) -> Result<Watch, String> {
+    let mut args = args.iter();
+    let mut day = None;
+    let mut delay = Duration::from_millis(100);
+    let mut paused = false;
+    let mut input_args = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(args.next())?),
+            "--delay" => {
+                let value = args.next().ok_or("Missing value for --delay")?;
+                let millis = value.parse().map_err(|_| format!("Invalid delay: {}", value))?;
+                delay = Duration::from_millis(millis);
+            }
+            "--paused" => paused = true,
+            _ => input_args.push(arg.clone()),
+        }
+    }
+
+    let day = day.ok_or("Pass the day to watch with --day <day>")?;
+    let source = Source::from_args(&input_args).map_err(|e| e.to_string())?;
+
+    Ok(Watch { day, source, delay, paused })
+}
+
+fn parse_bench(args: &[String]) -> Result<Bench, String> {
+    let mut args = args.iter();
+    let mut settings = Settings::default();
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut run_args = vec![];
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--warmup" => {
+                let value = value()?;
+                settings.warmup = value.parse().map_err(|_| format!("Invalid warm-up count: {}", value))?;
+            }
+            "--iterations" => {
+                let value = value()?;
+                settings.iterations = value
+                    .parse()
+                    .ok()
+                    .filter(|&iterations| iterations > 0)
+                    .ok_or(format!("Invalid iteration count: {}", value))?;
+            }
+            "--save" => save = Some(PathBuf::from(value()?)),
+            "--baseline" => baseline = Some(PathBuf::from(value()?)),
+            "--threshold" => {
+                let value = value()?;
+                threshold = value
+                    .parse()
+                    .ok()
+                    .filter(|threshold: &f64| *threshold >= 0.0)
+                    .ok_or(format!("Invalid threshold: {}", value))?;
+            }
+            _ => run_args.push(arg.clone()),
+        }
+    }
+
+    Ok(Bench {
+        run: parse_run(&run_args)?,
+        settings,
+        save,
+        baseline,
+        threshold: threshold / 100.0,
+    })
+}
+
+fn parse_verify(args: &[String]) -> Result<Verify, String> {
+    let mut args = args.iter();
+    let mut answers = None;
+    let mut record = false;
+    let mut overwrite = false;
+    let mut run_args = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => answers = Some(PathBuf::from(args.next().ok_or("Missing value for --answers")?)),
+            "--record" => record = true,
+            "--overwrite" => overwrite = true,
+            _ => run_args.push(arg.clone()),
+        }
+    }
+
+    if !run_args.iter().any(|arg| arg == "--day" || arg == "--all") {
+        run_args.push("--all".to_string());
+    }
+    let mut run = parse_run(&run_args)?;
+    // Answers are stored per input file, so don't guess between stdin and inputs/
+    if run.source == Source::Default {
+        run.source = Source::Inputs;
+    }
+
+    Ok(Verify {
+        run,
+        answers: answers.unwrap_or_else(known::default_path),
+        record: record || overwrite,
+        overwrite,
+    })
+}
+
+fn parse_check(args: &[String]) -> Result<Check, String> {
+    let mut args = args.iter();
+    let mut settings = gen::Settings {
+        seed: random_seed(),
+        ..gen::Settings::default()
+    };
+    let mut run_args = vec![];
+
+    while let Some(arg) = args.next() {
+        let mut number = |name: &str| {
+            let value = args.next().ok_or(format!("Missing value for {}", arg))?;
+            value.parse().map_err(|_| format!("Invalid {}: {}", name, value))
+        };
+        match arg.as_str() {
+            "--cases" => settings.cases = number("number of cases")? as usize,
+            "--seed" => settings.seed = number("seed")?,
+            "--size" => settings.max_size = (number("size")? as usize).max(1),
+            _ => run_args.push(arg.clone()),
+        }
+    }
+
+    let run = parse_run(&run_args)?;
+    if run.source != Source::Default {
+        return Err("Check generates its own inputs, it doesn't take one".to_string());
+    }
+
+    Ok(Check {
+        selection: run.selection,
+        part: run.part,
+        settings,
+    })
+}
+
+// A different seed every run finds more, the seed is printed so a failure can be repeated
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|since| since.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+fn find_day(number: u8) -> Result<&'static dyn Day, String> {
+    days()
+        .into_iter()
+        .find(|day| day.day() == number)
+        .ok_or(format!("Day {} is not registered", number))
+}
+
+fn watch(options: Watch) -> Result<(), String> {
+    let day = find_day(options.day)?;
+    let input = options.source.read(options.day).map_err(|e| e.to_string())?;
+
+    let mut viewer = Viewer::stdout(options.delay);
+    if options.paused {
+        viewer = viewer.paused();
+    }
+
+    // The days can't handle write errors mid-simulation, so keep the first one for the end
+    let mut result = Ok(());
+    let watched = day.watch(&input.text, &input.params, &mut |frame| {
+        if result.is_ok() {
+            result = viewer.show(frame);
+        }
+    });
+
+    viewer.finish().and(result).map_err(|e| e.to_string())?;
+    watched
+}
+
+// Days without an input are reported and skipped when running all of them
+fn read_inputs(options: &Run) -> Result<Vec<(&'static dyn Day, RawInput)>, String> {
+    match options.selection {
+        Selection::Day(number) => {
+            let input = options.source.read(number).map_err(|e| e.to_string())?;
+            Ok(vec![(find_day(number)?, input)])
+        }
+        Selection::All => {
+            // Every day reads its own file, a piped stdin can only belong to one of them
+            let source = match &options.source {
+                Source::Default => Source::Inputs,
+                source => source.clone(),
+            };
+
+            Ok(days()
+                .into_iter()
+                .filter_map(|day| match source.read(day.day()) {
+                    Ok(input) => Some((day, input)),
+                    Err(error) => {
+                        println!("Day {:02}: {} ({})", day.day(), day.title(), error);
+                        None
+                    }
+                })
+                .collect())
+        }
+    }
+}
+
+fn bench(options: Bench) -> Result<(), String> {
+    let baseline = match &options.baseline {
+        Some(path) => bench::load(path).map_err(|e| format!("Can't read the baseline: {}", e))?,
+        None => vec![],
+    };
+
+    let inputs = read_inputs(&options.run)?;
+
+    let mut measurements = vec![];
+    let mut regressions = 0;
+    for (day, input) in inputs {
+        println!("Day {:02}: {}", day.day(), day.title());
+
+        for measurement in day.bench(&input.text, &input.params, options.run.part, &options.settings) {
+            let mut note = String::new();
+            let previous = baseline
+                .iter()
+                .find(|previous| (previous.day, previous.stage) == (measurement.day, measurement.stage));
+            if let Some(previous) = previous {
+                let change = measurement.stats.median.as_secs_f64() / previous.stats.median.as_secs_f64() - 1.0;
+                note = format!("  {:+.0}% vs {:.2?}", change * 100.0, previous.stats.median);
+                if measurement.stats.regression(&previous.stats, options.threshold).is_some() {
+                    note += " REGRESSION";
+                    regressions += 1;
+                }
+            }
+
+            let stats = measurement.stats;
+            println!(
+                "  {:<5}  {:>10.2?}  (min {:.2?}, max {:.2?}){}",
+                measurement.stage, stats.median, stats.min, stats.max, note
+            );
+            measurements.push(measurement);
+        }
+    }
+
+    if let Some(path) = &options.save {
+        bench::save(path, &measurements).map_err(|e| format!("Can't save the results: {}", e))?;
+    }
+
+    match regressions {
+        0 => Ok(()),
+        1 => Err("1 stage got slower than the baseline".to_string()),
+        _ => Err(format!("{} stages got slower than the baseline", regressions)),
+    }
+}
+
+fn verify(options: Verify) -> Result<(), String> {
+    let mut known = KnownAnswers::load(&options.answers).map_err(|e| format!("Can't read the known answers: {}", e))?;
+    let parts = match options.run.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+    let unsolved = Unsolved.to_string();
+
+    let mut failures = 0;
+    let mut recorded = 0;
+    let inputs = read_inputs(&options.run)?;
+    println!("Day  Part  {:<20}  {:<7}  Answer", "Input", "Result");
+
+    for (day, input) in inputs {
+        let label = &input.label;
+        let print_row = |part: Part, result: &str, answer: &str| {
+            println!("{:02}   {}     {:<20}  {:<7}  {}", day.day(), part, label, result, answer);
+        };
+
+        for &part in &parts {
+            // A day that panics shouldn't stop the others from being checked
+            let solve = || day.solve(&input.text, &input.params, Some(part));
+            let Ok(answers) = panic::catch_unwind(AssertUnwindSafe(solve)) else {
+                failures += 1;
+                print_row(part, "panic", "");
+                continue;
+            };
+            let answer = match part {
+                Part::One => answers.part1,
+                Part::Two => answers.part2,
+            }
+            .unwrap_or_default();
+
+            let verdict = known.check(day.day(), part, label, &answer);
+            let note = match &verdict {
+                Verdict::Fail { expected } if options.overwrite => {
+                    known.record(day.day(), part, label, &answer);
+                    recorded += 1;
+                    format!(" (recorded, was {})", expected)
+                }
+                Verdict::Fail { expected } => {
+                    failures += 1;
+                    format!(" (expected {})", expected)
+                }
+                Verdict::Missing if options.record && answer != unsolved => {
+                    known.record(day.day(), part, label, &answer);
+                    recorded += 1;
+                    " (recorded)".to_string()
+                }
+                _ => String::new(),
+            };
+            print_row(part, &verdict.to_string(), &format!("{}{}", answer, note));
+        }
+    }
+
+    if recorded > 0 {
+        known.save(&options.answers).map_err(|e| format!("Can't save the known answers: {}", e))?;
+        println!("\nRecorded {} answers in {}", recorded, options.answers.display());
+    }
+
+    match failures {
+        0 => Ok(()),
+        1 => Err("1 answer didn't match".to_string()),
+        _ => Err(format!("{} answers didn't match", failures)),
+    }
+}
+
+fn check(options: Check) -> Result<(), String> {
+    let days = match options.selection {
+        Selection::Day(number) => vec![find_day(number)?],
+        Selection::All => days(),
+    };
+
+    println!("Seed {}", options.settings.seed);
+    let mut failures = 0;
+    for day in days {
+        print!("Day {:02}: {}: ", day.day(), day.title());
+        match day.check(options.part, &options.settings) {
+            Ok(report) => println!(
+                "{} cases, {} answers compared, {} without a reference",
+                report.cases, report.compared, report.skipped
+            ),
+            Err(error @ CheckError::NoGenerator(_)) => {
+                println!("no generator");
+                if options.selection != Selection::All {
+                    return Err(error.to_string());
+                }
+            }
+            Err(error) => {
+                failures += 1;
+                println!("FAILED\n{}", error);
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        1 => Err("1 day disagrees with its reference".to_string()),
+        _ => Err(format!("{} days disagree with their reference", failures)),
+    }
+}
+
+fn run(options: Run) -> Result<(), String> {
+    let mut panics = 0;
+    for (day, input) in read_inputs(&options)? {
+        // Like `verify`, a day that panics shouldn't stop the others from running
+        match panic::catch_unwind(AssertUnwindSafe(|| day.solve(&input.text, &input.params, options.part))) {
+            Ok(answers) => print!("{}", answers),
+            Err(_) => {
+                panics += 1;
+                println!("Day {:02}: {} (panicked)", day.day(), day.title());
+            }
+        }
+    }
+
+    match panics {
+        0 => Ok(()),
+        1 => Err("1 day panicked".to_string()),
+        _ => Err(format!("{} days panicked", panics)),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match command {
+        Command::Run(options) => run(options),
+        Command::Watch(options) => watch(options),
+        Command::Bench(options) => bench(options),
+        Command::Verify(options) => verify(options),
+        Command::Check(options) => check(options),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn parse_run_args(line: &str) -> Run {
+        match parse_args(&args(line)).unwrap() {
+            Command::Run(run) => run,
+            command => panic!("Expected a run command, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn test_parse_single_day() {
+        let run = parse_run_args("run --day 17 --part 2 input.txt");
+
+        assert_eq!(run.selection, Selection::Day(17));
+        assert_eq!(run.part, Some(Part::Two));
+        assert_eq!(run.source, Source::File("input.txt".into()));
+    }
+
+    #[test]
+    fn test_parse_example() {
+        let run = parse_run_args("run --day 16 --example 2 --part 1");
+
+        assert_eq!(run.selection, Selection::Day(16));
+        assert_eq!(run.part, Some(Part::One));
+        assert_eq!(run.source, Source::Example("2".into()));
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let run = parse_run_args("run --all");
+
+        assert_eq!(run.selection, Selection::All);
+        assert_eq!(run.part, None);
+        assert_eq!(run.source, Source::Default);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(&args("")).is_err());
+        assert!(parse_args(&args("run")).is_err());
+        assert!(parse_args(&args("run --day")).is_err());
+        assert!(parse_args(&args("run --day 1 --part 3")).is_err());
+        assert!(parse_args(&args("run --all input.txt")).is_err());
+        assert!(parse_args(&args("run --all -")).is_err());
+        assert!(parse_args(&args("bench")).is_err());
+        assert!(parse_args(&args("bench --all --iterations 0")).is_err());
+        assert!(parse_args(&args("bench --day 1 --threshold -5")).is_err());
+        assert!(parse_args(&args("bench --day 1 --save")).is_err());
+        assert!(parse_args(&args("stopwatch --all")).is_err());
+        assert!(parse_args(&args("verify --answers")).is_err());
+        assert!(parse_args(&args("verify --all input.txt")).is_err());
+        assert!(parse_args(&args("check --day 13 input.txt")).is_err());
+        assert!(parse_args(&args("check --day 13 --cases many")).is_err());
+        assert!(parse_args(&args("watch")).is_err());
+        assert!(parse_args(&args("watch --day 6 --delay fast")).is_err());
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        let command = parse_args(&args("watch --day 6 --example --delay 20 --paused")).unwrap();
+
+        assert_eq!(
+            command,
+            Command::Watch(Watch {
+                day: 6,
+                source: Source::Example("".into()),
+                delay: Duration::from_millis(20),
+                paused: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        let command = parse_args(&args("bench --day 7 --part 2 --iterations 20 --warmup 0 --baseline old.txt --threshold 5"));
+        let Ok(Command::Bench(bench)) = command else {
+            panic!("Expected a bench command, got {:?}", command);
+        };
+
+        assert_eq!(bench.run.selection, Selection::Day(7));
+        assert_eq!(bench.run.part, Some(Part::Two));
+        assert_eq!(bench.run.source, Source::Default);
+        assert_eq!(bench.settings, Settings { warmup: 0, iterations: 20 });
+        assert_eq!(bench.save, None);
+        assert_eq!(bench.baseline, Some(PathBuf::from("old.txt")));
+        assert_eq!(bench.threshold, 0.05);
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        let command = parse_args(&args("verify --example --answers answers.txt --overwrite")).unwrap();
+
+        assert_eq!(
+            command,
+            Command::Verify(Verify {
+                run: Run {
+                    selection: Selection::All,
+                    part: None,
+                    source: Source::Example("".into()),
+                },
+                answers: PathBuf::from("answers.txt"),
+                record: true,
+                overwrite: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_verify_reads_the_inputs_directory() {
+        let Ok(Command::Verify(verify)) = parse_args(&args("verify --day 3 --part 1 --record")) else {
+            panic!("Expected a verify command");
+        };
+
+        assert_eq!(verify.run.selection, Selection::Day(3));
+        assert_eq!(verify.run.source, Source::Inputs);
+        assert!(verify.record && !verify.overwrite);
+    }
+
+    #[test]
+    fn test_parse_check() {
+        let command = parse_args(&args("check --day 13 --part 1 --cases 50 --seed 7 --size 3")).unwrap();
+
+        assert_eq!(
+            command,
+            Command::Check(Check {
+                selection: Selection::Day(13),
+                part: Some(Part::One),
+                settings: gen::Settings {
+                    cases: 50,
+                    seed: 7,
+                    max_size: 3,
+                    ..gen::Settings::default()
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_every_day_is_registered_once() {
+        let numbers: Vec<u8> = days().iter().map(|day| day.day()).collect();
+
+        assert_eq!(numbers, (1..=24).collect::<Vec<u8>>());
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::{Params, Part, Solution};
+
+/// How often every stage runs: the warm-up runs fill caches and aren't measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            warmup: 2,
+            iterations: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        };
+        // `pad` rather than `write!`, so the runner can line the stages up with `{:<5}`
+        f.pad(name)
+    }
+}
+
+impl TryFrom<&str> for Stage {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "part1" => Ok(Stage::Part1),
+            "part2" => Ok(Stage::Part2),
+            _ => Err(format!("Invalid stage: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Panics when there are no samples
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "Can't summarise zero samples");
+        samples.sort_unstable();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Stats {
+            median,
+            min: samples[0],
+            max: samples[samples.len() - 1],
+        }
+    }
+
+    /// How much slower the median is than `baseline`'s, when it's more than `threshold` slower.
+    /// Both are fractions, so 0.1 is 10%.
+    pub fn regression(&self, baseline: &Stats, threshold: f64) -> Option<f64> {
+        let baseline = baseline.median.as_secs_f64();
+        let change = self.median.as_secs_f64() / baseline - 1.0;
+
+        (baseline > 0.0 && change > threshold).then_some(change)
+    }
+}
+
+/// Timing of one stage of one day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+/// Run `f` `settings.warmup` times, then time it for `settings.iterations` runs
+pub fn measure<R>(settings: &Settings, mut f: impl FnMut() -> R) -> Stats {
+    for _ in 0..settings.warmup {
+        black_box(f());
+    }
+
+    let mut samples: Vec<Duration> = (0..settings.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(&mut samples)
+}
+
+/// Time parsing and the requested part, or both parts when `part` is `None`. The parts are timed
+/// on an input that was parsed up front, so parsing only counts towards `Stage::Parse`.
+pub fn bench<S>(input: &str, params: &Params, part: Option<Part>, settings: &Settings) -> Vec<Measurement>
+where
+    S: Solution,
+{
+    let measurement = |stage, stats| Measurement { day: S::DAY, stage, stats };
+    let mut measurements = vec![measurement(Stage::Parse, measure(settings, || S::parse_with(input, params)))];
+
+    let parsed = S::parse_with(input, params);
+    if part != Some(Part::Two) {
+        measurements.push(measurement(Stage::Part1, measure(settings, || S::part1(&parsed))));
+    }
+    if part != Some(Part::One) {
+        measurements.push(measurement(Stage::Part2, measure(settings, || S::part2(&parsed))));
+    }
+
+    measurements
+}
+
+const HEADER: &str = "# day stage median_ns min_ns max_ns";
+
+/// Read results written by `save`
+pub fn load(path: impl AsRef<Path>) -> io::Result<Vec<Measurement>> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)?;
+
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| {
+            parse_line(line).ok_or_else(|| {
+                let message = format!("{}: line {} isn't a benchmark result: {}", path.display(), index + 1, line);
+                io::Error::new(io::ErrorKind::InvalidData, message)
+            })
+        })
+        .collect()
+}
+
+/// Write `measurements` to `path`, keeping the results of other days and stages already in it
+pub fn save(path: impl AsRef<Path>, measurements: &[Measurement]) -> io::Result<()> {
+    let path = path.as_ref();
+    let mut results = match load(path) {
+        Ok(results) => results,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => vec![],
+        Err(error) => return Err(error),
+    };
+
+    results.retain(|old| !measurements.iter().any(|new| (new.day, new.stage) == (old.day, old.stage)));
+    results.extend(measurements);
+    results.sort_by_key(|measurement| (measurement.day, measurement.stage));
+
+    let mut text = format!("{}\n", HEADER);
+    for Measurement { day, stage, stats } in results {
+        text += &format!(
+            "{} {} {} {} {}\n",
+            day,
+            stage,
+            stats.median.as_nanos(),
+            stats.min.as_nanos(),
+            stats.max.as_nanos()
+        );
+    }
+
+    fs::write(path, text)
+}
+
+fn parse_line(line: &str) -> Option<Measurement> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [day, stage, median, min, max] = fields[..] else {
+        return None;
+    };
+    let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+
+    Some(Measurement {
+        day: day.parse().ok()?,
+        stage: Stage::try_from(stage).ok()?,
+        stats: Stats {
+            median: nanos(median)?,
+            min: nanos(min)?,
+            max: nanos(max)?,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&value| Duration::from_millis(value)).collect()
+    }
+
+    fn stats(median: u64) -> Stats {
+        Stats {
+            median: Duration::from_millis(median),
+            min: Duration::from_millis(median / 2),
+            max: Duration::from_millis(median * 2),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let odd = Stats::from_samples(&mut millis(&[5, 1, 9, 3, 4]));
+        assert_eq!(odd.median, Duration::from_millis(4));
+        assert_eq!(odd.min, Duration::from_millis(1));
+        assert_eq!(odd.max, Duration::from_millis(9));
+
+        let even = Stats::from_samples(&mut millis(&[4, 1, 2, 8]));
+        assert_eq!(even.median, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_measure_runs_warmup_and_iterations() {
+        let mut runs = 0;
+        measure(&Settings { warmup: 3, iterations: 5 }, || runs += 1);
+
+        assert_eq!(runs, 8);
+    }
+
+    #[test]
+    fn test_regression() {
+        assert_eq!(stats(100).regression(&stats(100), 0.1), None);
+        assert_eq!(stats(105).regression(&stats(100), 0.1), None);
+        assert_eq!(stats(200).regression(&stats(100), 0.1), Some(1.0));
+        assert_eq!(stats(50).regression(&stats(100), 0.1), None);
+        assert_eq!(stats(1).regression(&stats(0), 0.1), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.txt", std::process::id()));
+        let measurement = |day, stage, median| Measurement {
+            day,
+            stage,
+            stats: stats(median),
+        };
+
+        save(&path, &[measurement(7, Stage::Part1, 10), measurement(7, Stage::Part2, 20)]).unwrap();
+        save(&path, &[measurement(7, Stage::Part2, 30), measurement(1, Stage::Parse, 2)]).unwrap();
+
+        assert_eq!(
+            load(&path).unwrap(),
+            [
+                measurement(1, Stage::Parse, 2),
+                measurement(7, Stage::Part1, 10),
+                measurement(7, Stage::Part2, 30)
+            ]
+        );
+
+        fs::write(&path, "7 part3 1 2 3\n").unwrap();
+        let error = load(&path).unwrap_err();
+        assert!(error.to_string().contains("line 1"));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// Where a simulation starts repeating itself: the state after `start` steps comes back every
+/// `period` steps from then on.
+///
+/// The functions here take a `step` that returns the next state, or `None` when the simulation
+/// ends, like a guard walking off the map. A simulation that ends has no cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The first step with the same state as step `n`
+    pub fn earliest(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "repeats every {} steps from step {} on", self.period, self.start)
+    }
+}
+
+/// Floyd's tortoise and hare, which only keeps two states around
+pub fn floyd<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle> {
+    // The hare goes twice as fast, they meet somewhere in the cycle
+    let mut tortoise = step(&initial)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        let halfway = step(&hare)?;
+        hare = step(&halfway)?;
+    }
+
+    let start = find_start(&initial, &hare, &mut step)?;
+
+    let mut period = 1;
+    let mut runner = step(&tortoise)?;
+    while runner != tortoise {
+        runner = step(&runner)?;
+        period += 1;
+    }
+
+    Some(Cycle { start, period })
+}
+
+/// Brent's algorithm, which keeps two states around like `floyd` but calls `step` less often
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle> {
+    // The tortoise teleports to the hare every power of two steps, until the hare runs into it
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare)?;
+        period += 1;
+    }
+
+    // A hare that's a period ahead meets the tortoise where the cycle starts
+    let mut ahead = initial.clone();
+    for _ in 0..period {
+        ahead = step(&ahead)?;
+    }
+    let start = find_start(&initial, &ahead, &mut step)?;
+
+    Some(Cycle { start, period })
+}
+
+// Step both from `initial` and from `ahead`, which is a whole number of periods further, until they
+// are in the same state. That's where the cycle starts.
+fn find_start<S>(initial: &S, ahead: &S, step: &mut impl FnMut(&S) -> Option<S>) -> Option<usize>
+where
+    S: Clone + PartialEq,
+{
+    let mut start = 0;
+    let mut tortoise = initial.clone();
+    let mut hare = ahead.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(start)
+}
+
+/// Remember every state until one comes back, which needs more memory than `brent` but only
+/// steps through the start of the cycle and one period once
+pub fn first_repeat<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut state = initial;
+    let mut n = 0;
+
+    while !seen.contains_key(&state) {
+        let next = step(&state)?;
+        seen.insert(state, n);
+        state = next;
+        n += 1;
+    }
+
+    let start = seen[&state];
+    Some(Cycle { start, period: n - start })
+}
+
+/// The state after `n` steps, skipping every whole period once the cycle is found. `None` when the
+/// simulation ends before step `n`.
+pub fn fast_forward<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> Option<S>, n: usize) -> Option<S> {
+    // Finding the cycle takes a few times as many steps as it's long. When that's more than `n`,
+    // stepping there directly is quicker.
+    let mut steps = 0;
+    let cycle = brent(initial.clone(), |state| {
+        steps += 1;
+        if steps > 3 * n.max(1) { None } else { step(state) }
+    });
+
+    let mut state = initial;
+    for _ in 0..cycle.map_or(n, |cycle| cycle.earliest(n)) {
+        state = step(&state)?;
+    }
+
+    Some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2 and then 3, 4, 5, 6, 7 over and over
+    fn rho(&n: &u32) -> Option<u32> {
+        Some(if n == 7 { 3 } else { n + 1 })
+    }
+
+    #[test]
+    fn test_detection() {
+        let expected = Some(Cycle { start: 3, period: 5 });
+
+        assert_eq!(floyd(0, rho), expected);
+        assert_eq!(brent(0, rho), expected);
+        assert_eq!(first_repeat(0, rho), expected);
+        assert_eq!(brent(3, rho), Some(Cycle { start: 0, period: 5 }));
+        assert_eq!(brent(5, |&n| Some(n)), Some(Cycle { start: 0, period: 1 }));
+    }
+
+    #[test]
+    fn test_simulation_that_ends() {
+        let countdown = |&n: &u32| n.checked_sub(1);
+
+        assert_eq!(floyd(10, countdown), None);
+        assert_eq!(brent(10, countdown), None);
+        assert_eq!(first_repeat(10, countdown), None);
+        assert_eq!(fast_forward(10, countdown, 4), Some(6));
+        assert_eq!(fast_forward(10, countdown, 11), None);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let cycle = Cycle { start: 3, period: 5 };
+        assert_eq!(cycle.earliest(2), 2);
+        assert_eq!(cycle.earliest(1_000_000_000), 5);
+        assert_eq!(cycle.to_string(), "repeats every 5 steps from step 3 on");
+
+        assert_eq!(fast_forward(0, rho, 1_000_000_000), Some(5));
+        assert_eq!(fast_forward(0, rho, 2), Some(2));
+        assert_eq!(fast_forward(0, rho, 0), Some(0));
+    }
+}
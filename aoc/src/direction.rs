@@ -0,0 +1,222 @@
+use std::fmt;
+
+use crate::{Map, Pos};
+
+/// A compass heading on a `Map`, where north is up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const CARDINALS: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// Clockwise from north, in the same order as `Pos::NEIGHBOURS`
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub fn delta(self) -> Pos {
+        Pos::NEIGHBOURS[self as usize]
+    }
+
+    /// The heading that moves by `delta`, if it's a single step
+    pub fn from_delta(delta: Pos) -> Option<Direction> {
+        Pos::NEIGHBOURS
+            .iter()
+            .position(|&neighbour| neighbour == delta)
+            .map(|index| Direction::ALL[index])
+    }
+
+    /// Turn clockwise in steps of 45 degrees, negative steps turn anticlockwise
+    pub fn rotate(self, steps: isize) -> Direction {
+        Direction::ALL[(self as isize + steps).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn turn_around(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_cardinal(self) -> bool {
+        (self as usize).is_multiple_of(2)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !self.is_cardinal()
+    }
+
+    /// `^`, `>`, `v` and `<` for the cardinal headings
+    pub fn arrow(self) -> Option<char> {
+        match self {
+            Direction::North => Some('^'),
+            Direction::East => Some('>'),
+            Direction::South => Some('v'),
+            Direction::West => Some('<'),
+            _ => None,
+        }
+    }
+}
+
+/// Parses arrows (`^>v<`) and compass letters (`NESW`)
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' => Ok(Direction::North),
+            '>' | 'E' => Ok(Direction::East),
+            'v' | 'S' => Ok(Direction::South),
+            '<' | 'W' => Ok(Direction::West),
+            _ => Err(c),
+        }
+    }
+}
+
+/// Arrows for cardinal headings, compass abbreviations like `NE` for diagonal ones
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::NorthEast => write!(f, "NE"),
+            Direction::SouthEast => write!(f, "SE"),
+            Direction::SouthWest => write!(f, "SW"),
+            Direction::NorthWest => write!(f, "NW"),
+            cardinal => write!(f, "{}", cardinal.arrow().unwrap()),
+        }
+    }
+}
+
+/// Something moving around a map: a guard, a robot or a reindeer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Walker {
+    pub pos: Pos,
+    pub heading: Direction,
+}
+
+impl Walker {
+    pub fn new(pos: Pos, heading: Direction) -> Self {
+        Walker { pos, heading }
+    }
+
+    /// The position one step ahead
+    pub fn ahead(&self) -> Pos {
+        self.pos + self.heading.delta()
+    }
+
+    /// The tile one step ahead, `None` when that step leaves the map
+    pub fn peek<'a, T>(&self, map: &'a Map<T>) -> Option<&'a T> {
+        map.at_point(self.ahead())
+    }
+
+    pub fn step(&mut self) {
+        self.pos = self.ahead();
+    }
+
+    /// Step forward and return the new tile, `None` once the walker left the map. Stepping over
+    /// an edge that wraps comes back on the other side.
+    pub fn step_on<'a, T>(&mut self, map: &'a Map<T>) -> Option<&'a T> {
+        self.step();
+        self.pos = map.wrap(self.pos).unwrap_or(self.pos);
+        map.at_point(self.pos)
+    }
+
+    pub fn turn_right(&mut self) {
+        self.heading = self.heading.turn_right();
+    }
+
+    pub fn turn_left(&mut self) {
+        self.heading = self.heading.turn_left();
+    }
+
+    pub fn turn_around(&mut self) {
+        self.heading = self.heading.turn_around();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Topology;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::SouthEast.turn_around(), Direction::NorthWest);
+        assert_eq!(Direction::North.rotate(-1), Direction::NorthWest);
+        assert!(Direction::East.is_cardinal());
+        assert!(Direction::SouthWest.is_diagonal());
+    }
+
+    #[test]
+    fn test_deltas() {
+        assert_eq!(Direction::North.delta(), Pos::UP);
+        assert_eq!(Direction::East.delta(), Pos::RIGHT);
+        assert_eq!(Direction::SouthWest.delta(), Pos::DOWN + Pos::LEFT);
+
+        for direction in Direction::ALL {
+            assert_eq!(Direction::from_delta(direction.delta()), Some(direction));
+        }
+        assert_eq!(Direction::from_delta(Pos::new(2, 0)), None);
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let arrows: Vec<Direction> = "^>v<".chars().map(|c| Direction::try_from(c).unwrap()).collect();
+        let compass: Vec<Direction> = "NESW".chars().map(|c| Direction::try_from(c).unwrap()).collect();
+
+        assert_eq!(arrows, Direction::CARDINALS);
+        assert_eq!(compass, Direction::CARDINALS);
+        assert_eq!(Direction::try_from('x'), Err('x'));
+        assert_eq!(arrows.iter().map(|d| d.to_string()).collect::<String>(), "^>v<");
+        assert_eq!(Direction::NorthEast.to_string(), "NE");
+    }
+
+    #[test]
+    fn test_walker() {
+        let map: Map<char> = Map::from("..\n.#");
+        let mut walker = Walker::new(Pos::ZERO, Direction::East);
+
+        assert_eq!(walker.peek(&map), Some(&'.'));
+        walker.turn_right();
+        assert_eq!(walker.step_on(&map), Some(&'.'));
+        walker.turn_left();
+        assert_eq!(walker.peek(&map), Some(&'#'));
+        walker.turn_around();
+        assert_eq!(walker.step_on(&map), None);
+        assert_eq!(walker, Walker::new(Pos::new(1, -1), Direction::West));
+    }
+
+    #[test]
+    fn test_walker_wraps() {
+        let map: Map<char> = Map::from("ab
+cd").with_topology(Topology::Toroidal);
+        let mut walker = Walker::new(Pos::ZERO, Direction::North);
+
+        assert_eq!(walker.peek(&map), Some(&'c'));
+        assert_eq!(walker.step_on(&map), Some(&'c'));
+        assert_eq!(walker.pos, Pos::new(1, 0));
+    }
+}
@@ -0,0 +1,334 @@
+use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::{normalise, solve_with, Params, Part, Solution};
+
+/// Generates the `main` of a day's `tests/examples.rs`, which runs every example in the day's
+/// directory against its `.expected` sidecar. The test needs `harness = false`:
+///
+/// ```toml
+/// [[test]]
+/// name = "examples"
+/// harness = false
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ty) => {
+        fn main() -> std::process::ExitCode {
+            $crate::examples::harness::<$solution>(env!("CARGO_MANIFEST_DIR"))
+        }
+    };
+}
+
+/// One part of one example file, with the answer its sidecar expects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub file: PathBuf,
+    pub part: Part,
+    pub expected: String,
+    /// The settings the example changes
+    pub params: Params,
+}
+
+impl Case {
+    /// `example2.txt part 1`
+    pub fn name(&self) -> String {
+        let file = self.file.file_name().unwrap_or_default().to_string_lossy();
+        format!("{} part {}", file, self.part)
+    }
+
+    pub fn run<S: Solution>(&self) -> Result<(), String> {
+        let input = fs::read_to_string(&self.file).map_err(|e| format!("Can't read {}: {}", self.file.display(), e))?;
+        let answers = solve_with::<S>(&normalise(&input), &self.params, Some(self.part));
+        let answer = match self.part {
+            Part::One => answers.part1,
+            Part::Two => answers.part2,
+        }
+        .unwrap_or_default();
+
+        if answer == self.expected {
+            Ok(())
+        } else {
+            Err(format!("expected {}, got {}", self.expected, answer))
+        }
+    }
+}
+
+/// The names `--example` understands: `example.txt`, `example2.txt` and `small_example.txt`
+pub fn is_example(name: &str) -> bool {
+    name.ends_with(".txt") && (name.starts_with("example") || name.ends_with("_example.txt"))
+}
+
+/// `example2.txt` is checked against `example2.expected`
+pub fn sidecar(file: &Path) -> PathBuf {
+    file.with_extension("expected")
+}
+
+/// What an example's sidecar holds
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub answers: Vec<(Part, String)>,
+    pub params: Params,
+}
+
+/// Read `part1: <answer>` and `part2: <answer>` lines, and `param <name>: <value>` lines for the
+/// settings the example changes. A part without a line isn't checked, which is how examples that
+/// only fit one part leave the other out.
+pub fn parse_expected(text: &str) -> Result<Expected, String> {
+    let mut expected = Expected::default();
+    let lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'));
+    for (index, line) in lines {
+        let invalid = || {
+            let forms = "`part1: <answer>`, `part2: <answer>` or `param <name>: <value>`";
+            format!("Line {} should be {}", index + 1, forms)
+        };
+        let (key, value) = line.split_once(':').ok_or_else(invalid)?;
+        let value = value.trim();
+        match key {
+            "part1" => expected.answers.push((Part::One, value.to_string())),
+            "part2" => expected.answers.push((Part::Two, value.to_string())),
+            _ => match key.strip_prefix("param ").map(str::trim) {
+                Some(name) if !name.is_empty() && !name.contains(char::is_whitespace) => {
+                    expected.params.set(name, value)
+                }
+                _ => return Err(invalid()),
+            },
+        }
+    }
+
+    Ok(expected)
+}
+
+/// An example file with the cases from its sidecar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub file: PathBuf,
+    /// `None` when there's no sidecar
+    pub cases: Option<Vec<Case>>,
+}
+
+/// Every example file in `directory`, sorted by name
+pub fn find(directory: &Path) -> Result<Vec<Example>, String> {
+    let entries = fs::read_dir(directory).map_err(|e| format!("Can't read {}: {}", directory.display(), e))?;
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.file_name().is_some_and(|name| is_example(&name.to_string_lossy())))
+        .collect();
+    files.sort();
+
+    files
+        .into_iter()
+        .map(|file| {
+            let sidecar = sidecar(&file);
+            let cases = match fs::read_to_string(&sidecar) {
+                Ok(text) => {
+                    let Expected { answers, params } =
+                        parse_expected(&text).map_err(|e| format!("{}: {}", sidecar.display(), e))?;
+                    let cases = answers.into_iter().map(|(part, expected)| Case {
+                        file: file.clone(),
+                        part,
+                        expected,
+                        params: params.clone(),
+                    });
+                    Some(cases.collect())
+                }
+                Err(_) => None,
+            };
+            Ok(Example { file, cases })
+        })
+        .collect()
+}
+
+/// Runs the examples of the day in `directory`, printing the results like libtest does.
+/// Understands enough of libtest's arguments for `cargo test <filter>`, `--exact`, `--skip`
+/// and `--list`.
+pub fn harness<S: Solution>(directory: &str) -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut filters = vec![];
+    let mut skips = vec![];
+    let mut exact = false;
+    let mut list = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--list" => list = true,
+            "--exact" => exact = true,
+            "--skip" => skips.extend(args.next()),
+            // The rest of libtest's options that take a value, ignored along with their value
+            "--test-threads" | "--format" | "--color" | "--logfile" | "-Z" => {
+                args.next();
+            }
+            flag if flag.starts_with('-') => {}
+            filter => filters.push(filter),
+        }
+    }
+
+    let examples = match find(Path::new(directory)) {
+        Ok(examples) => examples,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let matches = |name: &str, pattern: &str| if exact { name == pattern } else { name.contains(pattern) };
+    let selected = |name: &str| {
+        (filters.is_empty() || filters.iter().any(|filter| matches(name, filter)))
+            && !skips.iter().any(|skip| matches(name, skip))
+    };
+
+    let cases: Vec<&Case> = examples.iter().flat_map(|example| example.cases.iter().flatten()).collect();
+
+    // An example without a sidecar is one ignored test, and so is every part its sidecar leaves out
+    let mut ignored_tests = vec![];
+    for Example { file, cases } in &examples {
+        let name = file.file_name().unwrap_or_default().to_string_lossy();
+        let sidecar = sidecar(file).file_name().unwrap_or_default().to_string_lossy().to_string();
+        match cases {
+            None => ignored_tests.push((name.to_string(), format!("no {}", sidecar))),
+            Some(cases) => {
+                for part in [Part::One, Part::Two] {
+                    if !cases.iter().any(|case| case.part == part) {
+                        let reason = format!("{} has no part{}", sidecar, part);
+                        ignored_tests.push((format!("{} part {}", name, part), reason));
+                    }
+                }
+            }
+        }
+    }
+    let total = cases.len() + ignored_tests.len();
+
+    if list {
+        for case in cases.iter().filter(|case| selected(&case.name())) {
+            println!("{}: test", case.name());
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let mut filtered = 0;
+    let mut failures = vec![];
+    let mut ignored = 0;
+    println!("\nrunning {} test{}", total, if total == 1 { "" } else { "s" });
+
+    for case in cases {
+        let name = case.name();
+        if !selected(&name) {
+            filtered += 1;
+            continue;
+        }
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| case.run::<S>())).unwrap_or_else(|_| Err("panicked".to_string()));
+        match result {
+            Ok(()) => println!("test {} ... ok", name),
+            Err(error) => {
+                println!("test {} ... FAILED", name);
+                failures.push((name, error));
+            }
+        }
+    }
+
+    for (name, reason) in ignored_tests {
+        if !selected(&name) {
+            filtered += 1;
+            continue;
+        }
+
+        println!("test {} ... ignored, {}", name, reason);
+        ignored += 1;
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:\n");
+        for (name, error) in &failures {
+            println!("---- {} ----\n{}\n", name, error);
+        }
+    }
+
+    let passed = total - filtered - ignored - failures.len();
+    println!(
+        "\ntest result: {}. {} passed; {} failed; {} ignored; 0 measured; {} filtered out\n",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        passed,
+        failures.len(),
+        ignored,
+        filtered
+    );
+
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_example() {
+        assert!(is_example("example.txt"));
+        assert!(is_example("example2.txt"));
+        assert!(is_example("big_box_example.txt"));
+        assert!(!is_example("example.expected"));
+        assert!(!is_example("input.txt"));
+    }
+
+    #[test]
+    fn test_parse_expected() {
+        let expected = parse_expected("# from the puzzle text\npart1: 10092\n\npart2:  co,de,ka,ta \n").unwrap();
+
+        assert_eq!(expected.answers, [(Part::One, "10092".to_string()), (Part::Two, "co,de,ka,ta".to_string())]);
+        assert_eq!(expected.params, Params::default());
+
+        let forms = "`part1: <answer>`, `part2: <answer>` or `param <name>: <value>`";
+        assert_eq!(parse_expected("part 1: 10").unwrap_err(), format!("Line 1 should be {}", forms));
+        assert_eq!(parse_expected("part1: 1\nparam: 7").unwrap_err(), format!("Line 2 should be {}", forms));
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let expected = parse_expected("param size: 7\nparam  bytes : 12\npart1: 22").unwrap();
+
+        assert_eq!(expected.answers, [(Part::One, "22".to_string())]);
+        assert_eq!(expected.params.get_or("size", 71), Ok(7));
+        assert_eq!(expected.params.get_or("bytes", 1024), Ok(12));
+    }
+
+    #[test]
+    fn test_find() {
+        let directory = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        for (name, contents) in [
+            ("example.txt", "1"),
+            ("example.expected", "part2: 4"),
+            ("small_example.txt", "2"),
+            ("third_example.txt", "3"),
+            ("third_example.expected", "# nothing yet\n"),
+            ("notes.txt", ""),
+        ] {
+            fs::write(directory.join(name), contents).unwrap();
+        }
+
+        let examples = find(&directory).unwrap();
+
+        assert_eq!(examples.len(), 3);
+        let case = Case {
+            file: directory.join("example.txt"),
+            part: Part::Two,
+            expected: "4".to_string(),
+            params: Params::default(),
+        };
+        assert_eq!(case.name(), "example.txt part 2");
+        assert_eq!(examples[0].cases, Some(vec![case]));
+        assert_eq!(examples[1].file, directory.join("small_example.txt"));
+        assert_eq!(examples[1].cases, None);
+        assert_eq!(examples[2].cases, Some(vec![]));
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
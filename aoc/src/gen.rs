@@ -0,0 +1,354 @@
+use std::fmt;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{solve, Part, Solution};
+
+/// SplitMix64: small, seedable and random enough to generate puzzle inputs
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, panics when `n` is 0
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Can't pick a number below 0");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in `range`, panics when it's empty
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "Can't pick a number in the empty range {:?}", range);
+        let span = range.end.abs_diff(range.start);
+        range.start.wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Smaller inputs with one line left out, the default `Solution::shrink`
+pub fn shrink_lines(input: &str) -> Vec<String> {
+    without_each(&input.lines().collect::<Vec<_>>(), "\n")
+}
+
+/// Smaller inputs with one blank-line separated section left out
+pub fn shrink_sections(input: &str) -> Vec<String> {
+    without_each(&input.split("\n\n").collect::<Vec<_>>(), "\n\n")
+}
+
+fn without_each(parts: &[&str], separator: &str) -> Vec<String> {
+    if parts.len() <= 1 {
+        return vec![];
+    }
+
+    (0..parts.len())
+        .map(|skip| {
+            let kept: Vec<&str> = parts.iter().enumerate().filter(|&(i, _)| i != skip).map(|(_, part)| *part).collect();
+            kept.join(separator)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub cases: usize,
+    /// Case `i` is generated from `seed + i`, so `--seed <case seed> --cases 1` repeats a single case
+    pub seed: u64,
+    /// Passed to `Solution::generate` as a size between 1 and this
+    pub max_size: usize,
+    /// How many smaller inputs to try at most while shrinking a counterexample
+    pub shrink_limit: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            cases: 1000,
+            seed: 0,
+            max_size: 20,
+            shrink_limit: 2000,
+        }
+    }
+}
+
+/// What a check without counterexamples went through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Report {
+    pub cases: usize,
+    /// Parts compared against the reference, at most two per case
+    pub compared: usize,
+    /// Parts the reference had no answer for
+    pub skipped: usize,
+}
+
+/// A generated input on which the solution and the reference disagree, shrunk as far as it goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample {
+    pub day: u8,
+    pub part: Part,
+    pub seed: u64,
+    pub input: String,
+    pub expected: String,
+    pub actual: String,
+    pub shrinks: usize,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Day {:02} part {} disagrees with the reference on the case with seed {} (shrunk {} times)",
+            self.day, self.part, self.seed, self.shrinks
+        )?;
+        writeln!(f, "  expected: {}", self.expected)?;
+        writeln!(f, "  actual:   {}", self.actual)?;
+        write!(f, "--- input ---\n{}", self.input)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckError {
+    NoGenerator(u8),
+    Counterexample(Box<Counterexample>),
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckError::NoGenerator(day) => write!(f, "Day {} has no input generator", day),
+            CheckError::Counterexample(counterexample) => write!(f, "{}", counterexample),
+        }
+    }
+}
+
+impl std::error::Error for CheckError {}
+
+/// Compare the solution with its reference on `settings.cases` generated inputs, for the given
+/// part or both. The first disagreement is shrunk with `Solution::shrink` and returned.
+pub fn check<S: Solution>(part: Option<Part>, settings: &Settings) -> Result<Report, CheckError> {
+    let parts: Vec<Part> = [Part::One, Part::Two]
+        .into_iter()
+        .filter(|&candidate| part.is_none_or(|part| part == candidate))
+        .collect();
+    let mut report = Report {
+        cases: 0,
+        compared: 0,
+        skipped: 0,
+    };
+
+    for case in 0..settings.cases {
+        let seed = settings.seed.wrapping_add(case as u64);
+        let mut rng = Rng::new(seed);
+        let size = 1 + rng.below(settings.max_size.max(1));
+        let input = S::generate(&mut rng, size).ok_or(CheckError::NoGenerator(S::DAY))?;
+        report.cases += 1;
+
+        for &part in &parts {
+            let Some(expected) = reference::<S>(&input, part) else {
+                report.skipped += 1;
+                continue;
+            };
+            report.compared += 1;
+
+            let actual = answer::<S>(&input, part);
+            if actual != expected {
+                let mut counterexample = Counterexample {
+                    day: S::DAY,
+                    part,
+                    seed,
+                    input,
+                    expected,
+                    actual,
+                    shrinks: 0,
+                };
+                shrink::<S>(&mut counterexample, settings.shrink_limit);
+                return Err(CheckError::Counterexample(Box::new(counterexample)));
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+// Keep replacing the input with the first smaller one that still fails, until none do
+fn shrink<S: Solution>(counterexample: &mut Counterexample, limit: usize) {
+    let mut tries = 0;
+
+    'shrinking: while tries < limit {
+        for candidate in S::shrink(&counterexample.input) {
+            if tries == limit {
+                break 'shrinking;
+            }
+            tries += 1;
+
+            let Some(expected) = reference::<S>(&candidate, counterexample.part) else {
+                continue;
+            };
+            let actual = answer::<S>(&candidate, counterexample.part);
+            if actual != expected {
+                counterexample.input = candidate;
+                counterexample.expected = expected;
+                counterexample.actual = actual;
+                counterexample.shrinks += 1;
+                continue 'shrinking;
+            }
+        }
+        break;
+    }
+}
+
+// A reference that panics is treated like one without an answer, shrinking can make invalid inputs
+fn reference<S: Solution>(input: &str, part: Part) -> Option<String> {
+    panic::catch_unwind(|| S::reference(input, part)).ok().flatten()
+}
+
+fn answer<S: Solution>(input: &str, part: Part) -> String {
+    let answers = panic::catch_unwind(AssertUnwindSafe(|| solve::<S>(input, Some(part))));
+    match answers {
+        Ok(answers) => match part {
+            Part::One => answers.part1,
+            Part::Two => answers.part2,
+        }
+        .unwrap_or_default(),
+        Err(_) => "panicked".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums the numbers, but part 1 goes wrong once there's a number over 50
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+        const TITLE: &'static str = "Sum";
+
+        type Input = Vec<u64>;
+        type Part1 = u64;
+        type Part2 = u64;
+
+        fn parse(input: &str) -> Self::Input {
+            input.lines().map(|line| line.parse().unwrap()).collect()
+        }
+
+        fn part1(input: &Self::Input) -> Self::Part1 {
+            input.iter().map(|&n| n.min(50)).sum()
+        }
+
+        fn part2(input: &Self::Input) -> Self::Part2 {
+            input.iter().sum()
+        }
+
+        fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+            let numbers: Vec<String> = (0..size).map(|_| rng.range(0..60).to_string()).collect();
+            Some(numbers.join("\n"))
+        }
+
+        fn reference(input: &str, _: Part) -> Option<String> {
+            let sum: u64 = input.lines().map(|line| line.parse::<u64>().unwrap()).sum();
+            Some(sum.to_string())
+        }
+    }
+
+    struct NoGenerator;
+
+    impl Solution for NoGenerator {
+        const DAY: u8 = 2;
+        const TITLE: &'static str = "Nothing";
+
+        type Input = ();
+        type Part1 = u8;
+        type Part2 = u8;
+
+        fn parse(_: &str) -> Self::Input {}
+
+        fn part1(_: &Self::Input) -> Self::Part1 {
+            0
+        }
+
+        fn part2(_: &Self::Input) -> Self::Part2 {
+            0
+        }
+    }
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(7);
+        let mut again = Rng::new(7);
+        assert_eq!(rng.next_u64(), again.next_u64());
+
+        for _ in 0..1000 {
+            assert!((-3..4).contains(&rng.range(-3..4)));
+            assert!(rng.below(5) < 5);
+        }
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_shrink_helpers() {
+        assert_eq!(shrink_lines("a\nb\nc"), ["b\nc", "a\nc", "a\nb"]);
+        assert_eq!(shrink_sections("a\nb\n\nc"), ["c", "a\nb"]);
+        assert!(shrink_lines("a").is_empty());
+    }
+
+    #[test]
+    fn test_check_passes() {
+        let report = check::<Sum>(Some(Part::Two), &Settings::default()).unwrap();
+
+        assert_eq!(report.cases, 1000);
+        assert_eq!(report.compared, 1000);
+    }
+
+    #[test]
+    fn test_check_shrinks_counterexample() {
+        let Err(CheckError::Counterexample(counterexample)) = check::<Sum>(None, &Settings::default()) else {
+            panic!("Part 1 should disagree with the reference");
+        };
+
+        assert_eq!(counterexample.part, Part::One);
+        // Shrinking leaves just the number that's too big
+        assert_eq!(counterexample.input.lines().count(), 1);
+        assert!(counterexample.input.parse::<u64>().unwrap() > 50);
+    }
+
+    #[test]
+    fn test_check_without_generator() {
+        let error = check::<NoGenerator>(None, &Settings::default()).unwrap_err();
+
+        assert_eq!(error, CheckError::NoGenerator(2));
+    }
+}
@@ -0,0 +1,291 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+use crate::examples;
+use crate::Params;
+
+/// Where a day's input comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `inputs/dayNN.txt`, or stdin when something is piped in
+    Default,
+    /// `inputs/dayNN.txt`, even when stdin is piped
+    Inputs,
+    Stdin,
+    File(PathBuf),
+    /// One of the example files in the day's directory, see `example_filename`
+    Example(String),
+}
+
+/// A day's input as read from a `Source`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawInput {
+    /// Normalised so every day sees the same line endings
+    pub text: String,
+    /// Names where the input was read from the same way every time, so answers can be stored per
+    /// input. Piped stdin is `-`, even for `Source::Default`.
+    pub label: String,
+    /// The settings an example's sidecar changes, none for other inputs
+    pub params: Params,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Usage(String),
+    Stdin(io::Error),
+    File(PathBuf, io::Error),
+    NoDayDirectory(u8),
+    Sidecar(PathBuf, String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Usage(message) => write!(f, "{}", message),
+            InputError::Stdin(error) => write!(f, "Can't read stdin: {}", error),
+            InputError::File(path, error) => write!(f, "Can't read {}: {}", path.display(), error),
+            InputError::NoDayDirectory(day) => write!(f, "Can't find a day{:02}-* directory for the examples", day),
+            InputError::Sidecar(path, error) => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl Source {
+    /// Parse the input arguments: `-` for stdin, `--example [name]`, or a filename
+    pub fn from_args(args: &[String]) -> Result<Source, InputError> {
+        let mut source = Source::Default;
+        let mut args = args.iter().peekable();
+
+        while let Some(arg) = args.next() {
+            let next = match arg.as_str() {
+                "-" => Source::Stdin,
+                "--example" => {
+                    let name = args.next_if(|name| !name.starts_with('-')).cloned();
+                    Source::Example(name.unwrap_or_default())
+                }
+                flag if flag.starts_with("--") => {
+                    return Err(InputError::Usage(format!("Unknown option: {}", flag)))
+                }
+                path => Source::File(PathBuf::from(path)),
+            };
+
+            if source != Source::Default {
+                return Err(InputError::Usage("Only one input can be passed".to_string()));
+            }
+            source = next;
+        }
+
+        Ok(source)
+    }
+
+    /// Read the input for `day`, with the settings of the example it is
+    pub fn read(&self, day: u8) -> Result<RawInput, InputError> {
+        // Something like cron gives us an empty stdin, so fall back to the input file
+        if *self == Source::Default && !io::stdin().is_terminal() {
+            let text = read_stdin()?;
+            if !text.trim().is_empty() {
+                return Ok(RawInput {
+                    text: normalise(&text),
+                    label: Source::Stdin.label(day),
+                    params: Params::default(),
+                });
+            }
+        }
+
+        let mut params = Params::default();
+        let text = match self {
+            Source::Default | Source::Inputs => read_file(&default_path(day))?,
+            Source::Stdin => read_stdin()?,
+            Source::File(path) => read_file(path)?,
+            Source::Example(name) => {
+                let directory = day_directory(day).ok_or(InputError::NoDayDirectory(day))?;
+                let file = directory.join(example_filename(name));
+                params = read_params(&examples::sidecar(&file))?;
+                read_file(&file)?
+            }
+        };
+
+        Ok(RawInput {
+            text: normalise(&text),
+            label: self.label(day),
+            params,
+        })
+    }
+
+    // What `RawInput::label` is when the input came from this source
+    fn label(&self, day: u8) -> String {
+        match self {
+            Source::Default | Source::Inputs => format!("inputs/day{:02}.txt", day),
+            Source::Stdin => "-".to_string(),
+            Source::File(path) => path.display().to_string(),
+            Source::Example(name) => example_filename(name),
+        }
+    }
+}
+
+/// Read the input for a day's binary from the command line arguments
+pub fn input(day: u8) -> Result<RawInput, InputError> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    Source::from_args(&args)?.read(day)
+}
+
+/// Use `\n` line endings and drop trailing newlines
+pub fn normalise(text: &str) -> String {
+    text.replace("\r\n", "\n").trim_end_matches('\n').to_string()
+}
+
+/// `""` is `example.txt`, `"2"` is `example2.txt` and `"small"` is `small_example.txt`
+pub fn example_filename(name: &str) -> String {
+    if name.is_empty() {
+        "example.txt".to_string()
+    } else if name.ends_with(".txt") {
+        name.to_string()
+    } else if name.chars().all(|c| c.is_ascii_digit()) {
+        format!("example{}.txt", name)
+    } else {
+        format!("{}_example.txt", name)
+    }
+}
+
+fn read_stdin() -> Result<String, InputError> {
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text).map_err(InputError::Stdin)?;
+
+    Ok(text)
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|error| InputError::File(path.to_path_buf(), error))
+}
+
+// An example without a sidecar uses the real puzzle's settings
+fn read_params(sidecar: &Path) -> Result<Params, InputError> {
+    match fs::read_to_string(sidecar) {
+        Ok(text) => examples::parse_expected(&text)
+            .map(|expected| expected.params)
+            .map_err(|error| InputError::Sidecar(sidecar.to_path_buf(), error)),
+        Err(_) => Ok(Params::default()),
+    }
+}
+
+// Look for inputs/dayNN.txt in the current directory or one of its parents, so the binaries
+// work from both the repository root and a day's directory
+fn default_path(day: u8) -> PathBuf {
+    find_upwards(&PathBuf::from(format!("inputs/day{:02}.txt", day)))
+}
+
+/// `path` in the current directory or the closest parent that has it, or `path` itself when none do
+pub(crate) fn find_upwards(path: &Path) -> PathBuf {
+    env::current_dir()
+        .ok()
+        .and_then(|cwd| {
+            cwd.ancestors()
+                .map(|dir| dir.join(path))
+                .find(|candidate| candidate.exists())
+        })
+        .unwrap_or(path.to_path_buf())
+}
+
+fn day_directory(day: u8) -> Option<PathBuf> {
+    let prefix = format!("day{:02}-", day);
+    let is_day = |path: &Path| {
+        path.file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with(&prefix))
+    };
+
+    let cwd = env::current_dir().ok()?;
+    cwd.ancestors().find_map(|dir| {
+        if is_day(dir) {
+            return Some(dir.to_path_buf());
+        }
+
+        fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| path.is_dir() && is_day(path))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(normalise("1,2\r\n3,4\r\n"), "1,2\n3,4");
+        assert_eq!(normalise("a\n\nb\n\n\n"), "a\n\nb");
+        assert_eq!(normalise("  indented\n"), "  indented");
+    }
+
+    #[test]
+    fn test_example_filename() {
+        assert_eq!(example_filename(""), "example.txt");
+        assert_eq!(example_filename("2"), "example2.txt");
+        assert_eq!(example_filename("small"), "small_example.txt");
+        assert_eq!(example_filename("simple_example.txt"), "simple_example.txt");
+    }
+
+    #[test]
+    fn test_source_from_args() {
+        assert_eq!(Source::from_args(&args("")).unwrap(), Source::Default);
+        assert_eq!(Source::from_args(&args("-")).unwrap(), Source::Stdin);
+        assert_eq!(Source::from_args(&args("input.txt")).unwrap(), Source::File("input.txt".into()));
+        assert_eq!(Source::from_args(&args("--example")).unwrap(), Source::Example("".into()));
+        assert_eq!(Source::from_args(&args("--example 2")).unwrap(), Source::Example("2".into()));
+    }
+
+    #[test]
+    fn test_source_from_args_errors() {
+        assert!(Source::from_args(&args("a.txt b.txt")).is_err());
+        assert!(Source::from_args(&args("--example -")).is_err());
+        assert!(Source::from_args(&args("--verbose")).is_err());
+    }
+
+    #[test]
+    fn test_source_label() {
+        assert_eq!(Source::Default.label(7), "inputs/day07.txt");
+        assert_eq!(Source::Inputs.label(17), "inputs/day17.txt");
+        assert_eq!(Source::Example("2".into()).label(7), "example2.txt");
+        assert_eq!(Source::File("my/input.txt".into()).label(7), "my/input.txt");
+    }
+
+    #[test]
+    fn test_read_example_params() {
+        let example = Source::Example("".into()).read(18).unwrap();
+        assert!(example.text.starts_with("5,4\n4,2\n"));
+        assert_eq!(example.params.get_or("size", 71), Ok(7));
+        assert_eq!(example.label, "example.txt");
+
+        // Day 19's example has no settings
+        assert_eq!(Source::Example("".into()).read(19).unwrap().params, Params::default());
+    }
+
+    #[test]
+    fn test_read_file() {
+        let path = env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        fs::write(&path, "1 2\r\n3 4\r\n").unwrap();
+
+        let input = Source::File(path.clone()).read(1).unwrap();
+        assert_eq!(input.text, "1 2\n3 4");
+        assert_eq!(input.label, path.display().to_string());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        let error = Source::File("does/not/exist.txt".into()).read(1).unwrap_err();
+
+        assert!(error.to_string().starts_with("Can't read does/not/exist.txt"));
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::input::find_upwards;
+use crate::Part;
+
+const HEADER: &str = "# day part input answer";
+
+/// `inputs/answers.txt`, next to the inputs since the answers to them aren't committed either
+pub fn default_path() -> PathBuf {
+    find_upwards(Path::new("inputs/answers.txt"))
+}
+
+/// How an answer compares to the known one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let verdict = match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "FAIL",
+            Verdict::Missing => "missing",
+        };
+        f.pad(verdict)
+    }
+}
+
+/// Confirmed answers, per day, part and input. The input is named by `Source::label`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    answers: BTreeMap<(u8, Part, String), String>,
+}
+
+impl KnownAnswers {
+    /// A file that doesn't exist yet has no answers in it
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(KnownAnswers::default()),
+            Err(error) => return Err(error),
+        };
+
+        let mut known = KnownAnswers::default();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (day, part, input, answer) = parse_line(line).ok_or_else(|| {
+                let message = format!("{}: line {} isn't a known answer: {}", path.display(), index + 1, line);
+                io::Error::new(io::ErrorKind::InvalidData, message)
+            })?;
+            known.answers.insert((day, part, input), answer.to_string());
+        }
+
+        Ok(known)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(directory) = path.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+            fs::create_dir_all(directory)?;
+        }
+
+        let mut text = format!("{}\n", HEADER);
+        for ((day, part, input), answer) in &self.answers {
+            text += &format!("{} {} {} {}\n", day, part, escape(input), answer);
+        }
+
+        fs::write(path, text)
+    }
+
+    pub fn get(&self, day: u8, part: Part, input: &str) -> Option<&str> {
+        self.answers.get(&(day, part, input.to_string())).map(String::as_str)
+    }
+
+    /// Store `answer`, returning the one it replaced
+    pub fn record(&mut self, day: u8, part: Part, input: &str, answer: &str) -> Option<String> {
+        self.answers.insert((day, part, input.to_string()), answer.to_string())
+    }
+
+    pub fn check(&self, day: u8, part: Part, input: &str, answer: &str) -> Verdict {
+        match self.get(day, part, input) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+// Inputs are named by paths, which can have spaces in them. Those and `%` are written like in a
+// URL, so the input stays one field.
+fn escape(input: &str) -> String {
+    let mut escaped = String::new();
+    for c in input.chars() {
+        if c == '%' || c.is_whitespace() {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                escaped += &format!("%{:02X}", byte);
+            }
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+fn unescape(input: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut rest = input.as_bytes();
+    while let Some((&byte, after)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(after.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &after[2..];
+        } else {
+            bytes.push(byte);
+            rest = after;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+// The answer is the rest of the line, so it can hold anything but a newline
+fn parse_line(line: &str) -> Option<(u8, Part, String, &str)> {
+    let mut fields = line.splitn(4, ' ');
+    let day = fields.next()?.parse().ok()?;
+    let part = Part::try_from(fields.next()?).ok()?;
+    let input = fields.next().filter(|input| !input.is_empty())?;
+    let answer = fields.next()?;
+
+    Some((day, part, unescape(input)?, answer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let mut known = KnownAnswers::default();
+        known.record(17, Part::Two, "inputs/day17.txt", "4,6,3,5");
+
+        assert_eq!(known.check(17, Part::Two, "inputs/day17.txt", "4,6,3,5"), Verdict::Pass);
+        assert_eq!(
+            known.check(17, Part::Two, "inputs/day17.txt", "4,6,3"),
+            Verdict::Fail {
+                expected: "4,6,3,5".to_string()
+            }
+        );
+        assert_eq!(known.check(17, Part::Two, "example.txt", "4,6,3,5"), Verdict::Missing);
+        assert_eq!(known.check(17, Part::One, "inputs/day17.txt", "4,6,3,5"), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("aoc-known-{}", std::process::id())).join("answers.txt");
+        let mut known = KnownAnswers::default();
+        known.record(23, Part::Two, "inputs/day23.txt", "co,de,ka,ta");
+        known.record(1, Part::One, "example.txt", "11");
+        assert_eq!(known.record(1, Part::One, "example.txt", "12"), Some("11".to_string()));
+
+        known.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(text, format!("{}\n1 1 example.txt 12\n23 2 inputs/day23.txt co,de,ka,ta\n", HEADER));
+        assert_eq!(KnownAnswers::load(&path).unwrap(), known);
+
+        fs::write(&path, "1 3 example.txt 12\n").unwrap();
+        assert!(KnownAnswers::load(&path).unwrap_err().to_string().contains("line 1"));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_inputs_with_spaces() {
+        let path = std::env::temp_dir().join(format!("aoc-known-spaces-{}", std::process::id())).join("answers.txt");
+        let mut known = KnownAnswers::default();
+        known.record(1, Part::One, "my inputs/day 1 (100%).txt", "11 22");
+
+        known.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(text, format!("{}\n1 1 my%20inputs/day%201%20(100%25).txt 11 22\n", HEADER));
+        let loaded = KnownAnswers::load(&path).unwrap();
+        assert_eq!(loaded.get(1, Part::One, "my inputs/day 1 (100%).txt"), Some("11 22"));
+
+        fs::write(&path, "1 1 bad%2 11\n").unwrap();
+        assert!(KnownAnswers::load(&path).is_err());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let known = KnownAnswers::load("does/not/exist.txt").unwrap();
+
+        assert!(known.is_empty());
+    }
+}
@@ -0,0 +1,530 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::Pos;
+
+/// A rectangular grid of tiles, stored row after row in a single `Vec`.
+///
+/// `x` is the row and `y` the column, so a tile lives at `data[x * width + y]`.
+#[derive(Debug)]
+pub struct Map<T> {
+    data: Vec<T>,
+    pub width: usize,
+    pub height: usize,
+    pub topology: Topology,
+}
+
+/// What happens when something steps off the edge of a map
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    /// Off the edge is outside the map
+    #[default]
+    Bounded,
+    /// Every edge wraps around to the opposite one, like the floor in day 14
+    Toroidal,
+    /// Leaving on the left comes back on the right and the other way around, top and bottom are bounded
+    WrapHorizontal,
+    /// Leaving at the top comes back at the bottom and the other way around, left and right are bounded
+    WrapVertical,
+}
+
+impl Topology {
+    fn wraps_rows(self) -> bool {
+        matches!(self, Topology::Toroidal | Topology::WrapVertical)
+    }
+
+    fn wraps_columns(self) -> bool {
+        matches!(self, Topology::Toroidal | Topology::WrapHorizontal)
+    }
+}
+
+/// Why a map couldn't be parsed. Lines and columns count from 1, like an editor does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMapError {
+    Empty,
+    /// Rows follow, but the first one has no tiles to give the map its width
+    EmptyFirstLine,
+    InvalidTile { tile: char, line: usize, column: usize },
+    RaggedRow { line: usize, expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMapError::Empty => write!(f, "The map is empty"),
+            ParseMapError::EmptyFirstLine => write!(f, "Line 1 is empty, the map should start with its first row"),
+            ParseMapError::InvalidTile { tile, line, column } => {
+                write!(f, "Invalid tile {:?} at line {}, column {}", tile, line, column)
+            }
+            ParseMapError::RaggedRow { line, expected, found } => {
+                write!(f, "Line {} is {} tiles wide, expected {} like the first line", line, found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMapError {}
+
+/// Panics on an invalid map, use `Map::try_parse` to handle the error instead
+impl<T> From<&str> for Map<T>
+where
+    T: From<char>,
+{
+    fn from(input: &str) -> Self {
+        Map::try_parse(input).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<T> fmt::Display for Map<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for x in row.iter() {
+                write!(f, "{}", x)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Default for Map<T> {
+    fn default() -> Self {
+        Map {
+            data: Vec::new(),
+            width: 0,
+            height: 0,
+            topology: Topology::Bounded,
+        }
+    }
+}
+
+// Written out so `clone_from` reuses the existing allocation instead of making a new one
+impl<T: Clone> Clone for Map<T> {
+    fn clone(&self) -> Self {
+        Map {
+            data: self.data.clone(),
+            width: self.width,
+            height: self.height,
+            topology: self.topology,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+        self.width = source.width;
+        self.height = source.height;
+        self.topology = source.topology;
+    }
+}
+
+impl<T> Index<Pos> for Map<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.at_point(pos)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} map", pos, self.height, self.width))
+    }
+}
+
+impl<T> IndexMut<Pos> for Map<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{} is outside the {}x{} map", pos, height, width))
+    }
+}
+
+impl<T: Clone> Map<T> {
+    /// A `height` rows by `width` columns map where every tile is `tile`
+    pub fn new(width: usize, height: usize, tile: T) -> Self {
+        Map {
+            data: vec![tile; width * height],
+            width,
+            height,
+            topology: Topology::Bounded,
+        }
+    }
+}
+
+impl<T> Map<T> {
+    /// Parse one tile per character, checking every row is as wide as the first
+    pub fn try_parse(input: &str) -> Result<Self, ParseMapError>
+    where
+        T: TryFrom<char>,
+    {
+        let mut data = vec![];
+        let mut width = 0;
+        let mut height = 0;
+
+        for (x, line) in input.lines().enumerate() {
+            let row_start = data.len();
+            for (y, c) in line.chars().enumerate() {
+                let tile = T::try_from(c).map_err(|_| ParseMapError::InvalidTile {
+                    tile: c,
+                    line: x + 1,
+                    column: y + 1,
+                })?;
+                data.push(tile);
+            }
+
+            let found = data.len() - row_start;
+            if x == 0 && found == 0 && input.lines().nth(1).is_some() {
+                return Err(ParseMapError::EmptyFirstLine);
+            } else if x == 0 {
+                width = found;
+            } else if found != width {
+                return Err(ParseMapError::RaggedRow { line: x + 1, expected: width, found });
+            }
+            height += 1;
+        }
+
+        if width == 0 {
+            return Err(ParseMapError::Empty);
+        }
+
+        Ok(Map {
+            data,
+            width,
+            height,
+            topology: Topology::Bounded,
+        })
+    }
+
+    /// Panics when the rows don't all have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == width), "Map rows have different lengths");
+
+        Map {
+            data: rows.into_iter().flatten().collect(),
+            width,
+            height,
+            topology: Topology::Bounded,
+        }
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Where `pos` ends up on the map once the edges that wrap are taken into account, or `None`
+    /// when it's off an edge that doesn't wrap
+    pub fn wrap(&self, pos: Pos) -> Option<Pos> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+
+        let x = if self.topology.wraps_rows() { pos.x.rem_euclid(self.height as isize) } else { pos.x };
+        let y = if self.topology.wraps_columns() { pos.y.rem_euclid(self.width as isize) } else { pos.y };
+        let (row, column) = Pos::new(x, y).to_index()?;
+
+        (row < self.height && column < self.width).then_some(Pos::new(x, y))
+    }
+
+    /// The position `delta` away from `pos`, following the topology
+    pub fn step(&self, pos: Pos, delta: Pos) -> Option<Pos> {
+        self.wrap(pos + delta)
+    }
+
+    /// The tiles from `from` on in steps of `delta`, not including `from` itself. Stops at an edge
+    /// that doesn't wrap, or when it comes back around to `from`.
+    pub fn ray(&self, from: Pos, delta: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        let start = self.wrap(from);
+        let mut current = start.filter(|_| delta != Pos::ZERO);
+
+        std::iter::from_fn(move || {
+            let next = self.step(current?, delta).filter(|&next| Some(next) != start);
+            current = next;
+            next.map(|pos| (pos, &self[pos]))
+        })
+    }
+
+    /// The tiles along the ray before the first one `stop` says to stop at
+    pub fn ray_until<'a>(
+        &'a self,
+        from: Pos,
+        delta: Pos,
+        stop: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        self.ray(from, delta).take_while(move |(_, tile)| !stop(tile))
+    }
+
+    /// The first tile along the ray that `hit` is true for, like the next obstacle ahead
+    pub fn first_hit(&self, from: Pos, delta: Pos, hit: impl Fn(&T) -> bool) -> Option<(Pos, &T)> {
+        self.ray(from, delta).find(|(_, tile)| hit(tile))
+    }
+
+    /// The tiles a straight line from `from` to `to` runs through, see `Pos::line_to`, leaving out
+    /// the ones outside the map
+    pub fn line(&self, from: Pos, to: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        from.line_to(to).filter_map(|pos| self.at_point(pos).map(|tile| (pos, tile)))
+    }
+
+    /// The tiles exactly on the straight line from `from` to `to`, see `Pos::lattice_points_to`,
+    /// leaving out the ones outside the map
+    pub fn lattice_points(&self, from: Pos, to: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        from.lattice_points_to(to).filter_map(|pos| self.at_point(pos).map(|tile| (pos, tile)))
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        let (x, y) = self.wrap(pos)?.to_index()?;
+        Some(x * self.width + y)
+    }
+
+    /// The tile at `pos`, or `None` when it's outside the map. Positions off an edge that wraps
+    /// are taken from the other side.
+    pub fn at_point(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|index| &self.data[index])
+    }
+
+    /// Like `at_point`, so rows and columns past an edge that wraps come from the other side
+    pub fn at(&self, x: usize, y: usize) -> Option<&T> {
+        self.at_point(Pos::from((x, y)))
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|index| &mut self.data[index])
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.index_of(pos).is_some()
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        self.set_point(Pos::from((x, y)), value);
+    }
+
+    /// Panics when `pos` is outside the map, like indexing
+    pub fn set_point(&mut self, pos: Pos, value: T) {
+        self[pos] = value;
+    }
+
+    pub fn row(&self, x: usize) -> &[T] {
+        &self.data[x * self.width..(x + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, x: usize) -> &mut [T] {
+        &mut self.data[x * self.width..(x + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, and an empty map has no rows anyway
+        self.data.chunks(self.width.max(1))
+    }
+
+    /// The tiles in column `y`, from top to bottom
+    pub fn column(&self, y: usize) -> impl Iterator<Item = &T> {
+        assert!(y < self.width, "Column {} is outside the {} wide map", y, self.width);
+        self.data.iter().skip(y).step_by(self.width)
+    }
+
+    /// Top, right, bottom and left neighbours, skipping the ones outside the map. Neighbours across
+    /// an edge that wraps are on the other side.
+    pub fn cardinal_neighbours(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours_by(pos, &Pos::CARDINALS)
+    }
+
+    /// All 8 neighbours clockwise from the top, skipping the ones outside the map
+    pub fn all_neighbours(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours_by(pos, &Pos::NEIGHBOURS)
+    }
+
+    fn neighbours_by<'a>(&'a self, pos: Pos, deltas: &'static [Pos]) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        deltas.iter().filter_map(move |&delta| {
+            let next = self.step(pos, delta)?;
+            Some((next, &self[next]))
+        })
+    }
+
+    fn pos_of(&self, index: usize) -> Pos {
+        Pos::from((index / self.width, index % self.width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.data.iter().enumerate().map(|(index, item)| (self.pos_of(index), item))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        let width = self.width;
+        self.data
+            .iter_mut()
+            .enumerate()
+            .map(move |(index, item)| (Pos::from((index / width, index % width)), item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbours_stay_in_bounds() {
+        let map: Map<char> = Map::from("ab\ncd");
+
+        let corner: Vec<(Pos, &char)> = map.cardinal_neighbours(Pos::ZERO).collect();
+        assert_eq!(corner, vec![(Pos::new(0, 1), &'b'), (Pos::new(1, 0), &'c')]);
+        assert_eq!(map.all_neighbours(Pos::new(1, 1)).count(), 3);
+        assert_eq!(map.at_point(Pos::new(-1, 0)), None);
+        assert!(!map.contains(Pos::new(0, 2)));
+    }
+
+    #[test]
+    fn test_topology() {
+        let torus: Map<char> = Map::from("abc\ndef").with_topology(Topology::Toroidal);
+
+        assert_eq!(torus.wrap(Pos::new(-1, 3)), Some(Pos::new(1, 0)));
+        assert_eq!(torus[Pos::new(5, -1)], 'f');
+        let corner: Vec<(Pos, &char)> = torus.cardinal_neighbours(Pos::ZERO).collect();
+        // Two rows high, so up and down are the same neighbour
+        assert_eq!(
+            corner,
+            vec![(Pos::new(1, 0), &'d'), (Pos::new(0, 1), &'b'), (Pos::new(1, 0), &'d'), (Pos::new(0, 2), &'c')]
+        );
+
+        let horizontal: Map<char> = Map::from("abc\ndef").with_topology(Topology::WrapHorizontal);
+        assert_eq!(horizontal.at_point(Pos::new(0, -1)), Some(&'c'));
+        assert_eq!(horizontal.at_point(Pos::new(-1, 0)), None);
+        let vertical: Map<char> = Map::from("abc\ndef").with_topology(Topology::WrapVertical);
+        assert_eq!(vertical.step(Pos::ZERO, Pos::UP), Some(Pos::new(1, 0)));
+        assert_eq!(vertical.step(Pos::ZERO, Pos::LEFT), None);
+
+        assert_eq!(torus.at(2, 4), Some(&'b'));
+        assert_eq!(torus.at(2, 4), torus.at_point(Pos::new(2, 4)));
+        assert_eq!(horizontal.at(0, 3), Some(&'a'));
+        assert_eq!(horizontal.at(2, 0), None);
+    }
+
+    #[test]
+    fn test_ray() {
+        let map: Map<char> = Map::from("abc\ndef");
+        let ray: String = map.ray(Pos::ZERO, Pos::RIGHT).map(|(_, &c)| c).collect();
+        assert_eq!(ray, "bc");
+        assert_eq!(map.ray(Pos::ZERO, Pos::ZERO).count(), 0);
+
+        // Around the torus until it's back where it started
+        let torus = map.with_topology(Topology::Toroidal);
+        let ray: String = torus.ray(Pos::new(0, 1), Pos::new(1, 1)).map(|(_, &c)| c).collect();
+        assert_eq!(ray, "faecd");
+    }
+
+    #[test]
+    fn test_ray_queries() {
+        let map: Map<char> = Map::from("..#.\n....\n#...");
+
+        assert_eq!(map.first_hit(Pos::ZERO, Pos::RIGHT, |&c| c == '#'), Some((Pos::new(0, 2), &'#')));
+        assert_eq!(map.first_hit(Pos::new(1, 0), Pos::RIGHT, |&c| c == '#'), None);
+        assert_eq!(map.ray_until(Pos::new(0, 3), Pos::LEFT, |&c| c == '#').count(), 0);
+        assert_eq!(map.ray_until(Pos::new(2, 3), Pos::LEFT, |&c| c == '#').count(), 2);
+
+        // Of 0,-2 then 1,1 then 2,4 only the middle one is on the map
+        let line: Vec<Pos> = map.lattice_points(Pos::new(0, -2), Pos::new(2, 4)).map(|(pos, _)| pos).collect();
+        assert_eq!(line, vec![Pos::new(1, 1)]);
+        let line: Vec<Pos> = map.line(Pos::new(0, -2), Pos::new(2, 4)).map(|(pos, _)| pos).collect();
+        assert_eq!(line, vec![Pos::new(1, 0), Pos::new(1, 1), Pos::new(1, 2), Pos::new(2, 3)]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let map: Map<char> = Map::from("abc\ndef");
+
+        assert_eq!((map.width, map.height), (3, 2));
+        assert_eq!(map.row(1), &['d', 'e', 'f']);
+        assert_eq!(map.rows().count(), 2);
+        assert_eq!(map.column(2).collect::<String>(), "cf");
+        // The end of a row doesn't run into the next one
+        assert_eq!(map.at(0, 3), None);
+        assert_eq!(map.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn test_index() {
+        let mut map = Map::new(3, 2, 0);
+        map[Pos::new(1, 2)] = 5;
+        *map.get_mut(Pos::new(0, 1)).unwrap() += 1;
+        map.set(1, 0, 7);
+
+        assert_eq!(map[Pos::new(1, 2)], 5);
+        assert_eq!(map.iter().map(|(_, &n)| n).collect::<Vec<_>>(), vec![0, 1, 0, 7, 0, 5]);
+        assert_eq!(map.iter().last().map(|(pos, _)| pos), Some(Pos::new(1, 2)));
+        assert_eq!(map.get_mut(Pos::new(2, 0)), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_outside() {
+        let map = Map::new(3, 2, 0);
+        let _ = map[Pos::new(0, 3)];
+    }
+
+    #[test]
+    fn test_clone_from() {
+        let original: Map<char> = Map::from("ab\ncd");
+        let mut copy: Map<char> = Map::from("xy\nzw");
+        copy.clone_from(&original);
+
+        assert_eq!(copy.to_string(), original.to_string());
+    }
+
+    #[test]
+    fn test_empty() {
+        let map: Map<char> = Map::default();
+
+        assert_eq!((map.width, map.height), (0, 0));
+        assert_eq!(map.rows().count(), 0);
+        assert_eq!(map.to_string(), "");
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = char;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '.' => Ok(Tile::Open),
+                '#' => Ok(Tile::Wall),
+                _ => Err(c),
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let map: Map<Tile> = Map::try_parse("..#\n#..").unwrap();
+
+        assert_eq!((map.width, map.height), (3, 2));
+        assert_eq!(map[Pos::new(1, 0)], Tile::Wall);
+    }
+
+    #[test]
+    fn test_try_parse_errors() {
+        assert_eq!(
+            Map::<Tile>::try_parse("..#\n#.x").unwrap_err(),
+            ParseMapError::InvalidTile { tile: 'x', line: 2, column: 3 }
+        );
+        assert_eq!(
+            Map::<Tile>::try_parse("..#\n#.\n...").unwrap_err(),
+            ParseMapError::RaggedRow { line: 2, expected: 3, found: 2 }
+        );
+        assert_eq!(Map::<Tile>::try_parse("").unwrap_err(), ParseMapError::Empty);
+        assert_eq!(Map::<Tile>::try_parse("\n").unwrap_err(), ParseMapError::Empty);
+        assert_eq!(Map::<Tile>::try_parse("\n..#\n#..").unwrap_err(), ParseMapError::EmptyFirstLine);
+        assert_eq!(
+            ParseMapError::EmptyFirstLine.to_string(),
+            "Line 1 is empty, the map should start with its first row"
+        );
+        assert_eq!(
+            Map::<Tile>::try_parse("..#\n#.x").unwrap_err().to_string(),
+            "Invalid tile 'x' at line 2, column 3"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Settings a puzzle gives in its text instead of the input, like how big the floor is. The
+/// examples often use smaller ones than the real puzzle, so an example's sidecar can set them
+/// with `param <name>: <value>` lines. Settings that aren't set keep the real puzzle's value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_string(), value.to_string());
+    }
+
+    /// The setting called `name`, or `default` when it isn't set
+    pub fn get_or<T>(&self, name: &str, default: T) -> Result<T, String>
+    where
+        T: FromStr<Err: fmt::Display>,
+    {
+        match self.values.get(name) {
+            Some(value) => value.parse().map_err(|e| format!("Can't use `{}` as the {}: {}", value, name, e)),
+            None => Ok(default),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_or() {
+        let mut params = Params::default();
+        params.set("size", "7");
+        params.set("bytes", "many");
+
+        assert_eq!(params.get_or("size", 71), Ok(7));
+        assert_eq!(params.get_or("wide", 101), Ok(101));
+        let error = params.get_or("bytes", 1024).unwrap_err();
+        assert_eq!(error, "Can't use `many` as the bytes: invalid digit found in string");
+    }
+}
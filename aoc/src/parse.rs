@@ -0,0 +1,305 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// What's wrong with the puzzle text, and on which line (counting from 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A blank-line separated part of the input, which knows the line it starts on so errors in it
+/// point at the right line of the whole input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    /// The lines with their number in the whole input
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+        let first = self.line;
+        self.text.lines().enumerate().map(move |(index, line)| (first + index, line))
+    }
+
+    /// Every line that isn't blank as a `T`
+    pub fn records<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.lines()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| parse_at(number, line))
+            .collect()
+    }
+
+    /// The whole section as one `T`, errors point at its first line
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        parse_at(self.line, self.text)
+    }
+
+    /// The value of the `key: value` line with this key
+    pub fn field<T>(&self, key: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let (number, value) = self
+            .lines()
+            .find_map(|(number, line)| line.split_once(':').filter(|(k, _)| k.trim() == key).map(|(_, v)| (number, v)))
+            .ok_or_else(|| ParseError::new(self.line, format!("No `{}: ...` line", key)))?;
+
+        parse_at(number, value.trim())
+    }
+}
+
+fn parse_at<T>(line: usize, text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse().map_err(|e| ParseError::new(line, format!("Can't parse `{}`: {}", text, e)))
+}
+
+/// The input split on blank lines. Any number of blank lines separates two sections.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (index, line) in input.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((number, begin)) = start.take() {
+                sections.push(section(input, number, begin, offset));
+            }
+        } else if start.is_none() {
+            start = Some((index + 1, offset));
+        }
+        offset += line.len();
+    }
+    if let Some((number, begin)) = start {
+        sections.push(section(input, number, begin, offset));
+    }
+
+    sections
+}
+
+fn section(input: &str, line: usize, begin: usize, end: usize) -> Section<'_> {
+    Section {
+        line,
+        text: input[begin..end].trim_end_matches(['\r', '\n']),
+    }
+}
+
+/// Exactly `N` sections, for puzzles like `rules\n\nupdates`
+pub fn exact_sections<const N: usize>(input: &str) -> Result<[Section<'_>; N], ParseError> {
+    let sections = sections(input);
+    let found = sections.len();
+    let line = sections.last().map_or(1, |section| section.line);
+
+    sections.try_into().map_err(|_| {
+        ParseError::new(line, format!("Expected {} sections separated by blank lines, found {}", N, found))
+    })
+}
+
+/// Every line of the input that isn't blank as a `T`
+pub fn records<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Section { line: 1, text: input }.records()
+}
+
+/// Every integer in `text`, in order: `p=7,6 v=-1,-3` gives `[7, 6, -1, -3]`. A `-` is only a
+/// sign when it doesn't follow a letter or digit, so ranges like `3-5` give `[3, 5]`. A number too
+/// big for an `i64` is an error.
+pub fn integers(text: &str) -> Result<Vec<i64>, String> {
+    let bytes = text.as_bytes();
+    let mut integers = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut start = i;
+        if i > 0 && matches!(bytes[i - 1], b'-' | b'+') && (i < 2 || !bytes[i - 2].is_ascii_alphanumeric()) {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let number = &text[start..i];
+        integers.push(number.parse().map_err(|_| format!("`{}` doesn't fit in an i64", number))?);
+    }
+
+    Ok(integers)
+}
+
+/// Exactly `N` integers, found the way `integers` finds them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Integers<const N: usize>(pub [i64; N]);
+
+impl<const N: usize> FromStr for Integers<N> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let integers = integers(s)?;
+        let found = integers.len();
+        integers
+            .try_into()
+            .map(Integers)
+            .map_err(|_| format!("Expected {} numbers, found {}", N, found))
+    }
+}
+
+/// Two values around a separator, like `47|53` or `3-5`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair<T, const SEPARATOR: char>(pub T, pub T);
+
+impl<T, const SEPARATOR: char> FromStr for Pair<T, SEPARATOR>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (left, right) = s.split_once(SEPARATOR).ok_or_else(|| format!("Expected `a{}b`", SEPARATOR))?;
+        let value = |text: &str| text.trim().parse::<T>().map_err(|e| format!("{}: {}", text.trim(), e));
+
+        Ok(Pair(value(left)?, value(right)?))
+    }
+}
+
+/// Values with a separator between them, like `75,47,61`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct List<T, const SEPARATOR: char>(pub Vec<T>);
+
+impl<T, const SEPARATOR: char> FromStr for List<T, SEPARATOR>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(SEPARATOR)
+            .map(|item| item.trim().parse::<T>().map_err(|e| format!("{}: {}", item.trim(), e)))
+            .collect::<Result<_, _>>()
+            .map(List)
+    }
+}
+
+/// A `key: value` line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field<T> {
+    pub key: String,
+    pub value: T,
+}
+
+impl<T> FromStr for Field<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s.split_once(':').ok_or("Expected `key: value`")?;
+        let value = value.trim().parse().map_err(|e| format!("{}: {}", value.trim(), e))?;
+
+        Ok(Field {
+            key: key.trim().to_string(),
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        let input = "Register A: 729\nRegister B: 0\n\n\nProgram: 0,1,5,4,3,0\n";
+        let sections = sections(input);
+
+        assert_eq!(
+            sections,
+            [
+                Section {
+                    line: 1,
+                    text: "Register A: 729\nRegister B: 0"
+                },
+                Section {
+                    line: 5,
+                    text: "Program: 0,1,5,4,3,0"
+                }
+            ]
+        );
+        assert_eq!(sections[0].lines().last(), Some((2, "Register B: 0")));
+        assert_eq!(sections[0].field::<u64>("Register A"), Ok(729));
+        assert_eq!(sections[1].field::<List<u8, ','>>("Program"), Ok(List(vec![0, 1, 5, 4, 3, 0])));
+        assert_eq!(sections[0].field::<u64>("Register C").unwrap_err().to_string(), "Line 1: No `Register C: ...` line");
+
+        let error = exact_sections::<3>(input).unwrap_err();
+        assert_eq!(error, ParseError::new(5, "Expected 3 sections separated by blank lines, found 2"));
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(integers("p=7,6 v=-1,-3").unwrap(), [7, 6, -1, -3]);
+        assert_eq!(integers("Button A: X+94, Y+34").unwrap(), [94, 34]);
+        assert_eq!(integers("3-5 x-1 -2").unwrap(), [3, 5, 1, -2]);
+        assert_eq!(integers("no numbers").unwrap(), []);
+        assert_eq!(integers("-9223372036854775808").unwrap(), [i64::MIN]);
+
+        let error = integers("1, 99999999999999999999, 3").unwrap_err();
+        assert_eq!(error, "`99999999999999999999` doesn't fit in an i64");
+        let error = "p=1,-9223372036854775809".parse::<Integers<2>>().unwrap_err();
+        assert_eq!(error, "`-9223372036854775809` doesn't fit in an i64");
+        assert_eq!("p=0,4 v=3,-3".parse::<Integers<4>>(), Ok(Integers([0, 4, 3, -3])));
+        assert_eq!("p=0,4".parse::<Integers<4>>().unwrap_err(), "Expected 4 numbers, found 2");
+    }
+
+    #[test]
+    fn test_records() {
+        let rules: Vec<Pair<u8, '|'>> = records("47|53\n97|13\n").unwrap();
+        assert_eq!(rules, [Pair(47, 53), Pair(97, 13)]);
+        assert_eq!("2-4".parse::<Pair<u8, '-'>>(), Ok(Pair(2, 4)));
+
+        let fields: Vec<Field<u8>> = records("x00: 1\ny01: 0").unwrap();
+        assert_eq!(fields[1], Field { key: "y01".to_string(), value: 0 });
+
+        let error = records::<Pair<u8, '|'>>("47|53\n97|x").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.to_string(), "Line 2: Can't parse `97|x`: x: invalid digit found in string");
+    }
+}
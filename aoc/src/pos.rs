@@ -0,0 +1,297 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position on a grid, or the offset between two positions.
+///
+/// Uses the same axes as `Map`: `x` picks the row and `y` the column, so `Pos::UP` is `(-1, 0)`.
+/// Components are signed, so stepping off the top or left edge gives a negative position
+/// instead of wrapping around.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Pos {
+    pub const ZERO: Pos = Pos::new(0, 0);
+    pub const UP: Pos = Pos::new(-1, 0);
+    pub const RIGHT: Pos = Pos::new(0, 1);
+    pub const DOWN: Pos = Pos::new(1, 0);
+    pub const LEFT: Pos = Pos::new(0, -1);
+
+    /// Top, right, bottom, left
+    pub const CARDINALS: [Pos; 4] = [Pos::UP, Pos::RIGHT, Pos::DOWN, Pos::LEFT];
+
+    /// Clockwise from the top: top, top right, right, bottom right, ...
+    pub const NEIGHBOURS: [Pos; 8] = [
+        Pos::UP,
+        Pos::new(-1, 1),
+        Pos::RIGHT,
+        Pos::new(1, 1),
+        Pos::DOWN,
+        Pos::new(1, -1),
+        Pos::LEFT,
+        Pos::new(-1, -1),
+    ];
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Pos { x, y }
+    }
+
+    pub fn manhattan(self, other: Pos) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Pos) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Each component reduced to -1, 0 or 1
+    pub fn signum(self) -> Pos {
+        Pos::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Wrap around a grid of `size`, for positions that went off either edge
+    pub fn rem_euclid(self, size: Pos) -> Pos {
+        Pos::new(self.x.rem_euclid(size.x), self.y.rem_euclid(size.y))
+    }
+
+    /// Grid indexes, or `None` when either component is negative
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+
+    /// Every position at exactly `radius` steps, going clockwise around `self` from straight up
+    pub fn ring(self, radius: usize) -> impl Iterator<Item = Pos> {
+        let radius = radius as isize;
+        // Each side goes diagonally from one corner of the ring to the next. A ring of radius 0 is
+        // only `self`, as a single side of one position.
+        let corners = [Pos::UP, Pos::RIGHT, Pos::DOWN, Pos::LEFT];
+        let directions = [Pos::DOWN + Pos::RIGHT, Pos::DOWN + Pos::LEFT, Pos::UP + Pos::LEFT, Pos::UP + Pos::RIGHT];
+        let sides = if radius == 0 { 1 } else { 4 };
+
+        (0..sides).flat_map(move |side| {
+            (0..radius.max(1)).map(move |step| self + corners[side] * radius + directions[side] * step)
+        })
+    }
+
+    /// Every position at most `radius` steps away, starting with `self`
+    pub fn diamond(self, radius: usize) -> impl Iterator<Item = Pos> {
+        (0..=radius).flat_map(move |distance| self.ring(distance))
+    }
+
+    /// The smallest step in the same direction that still lands on whole positions, so `(4, -6)`
+    /// becomes `(2, -3)`
+    pub fn reduced(self) -> Pos {
+        match gcd(self.x.unsigned_abs(), self.y.unsigned_abs()) {
+            0 => self,
+            divisor => Pos::new(self.x / divisor as isize, self.y / divisor as isize),
+        }
+    }
+
+    /// The positions a straight line to `other` runs through, both ends included, with one step
+    /// along the longer axis at a time like Bresenham's algorithm. Every position touches the
+    /// one before, at least diagonally.
+    pub fn line_to(self, other: Pos) -> impl Iterator<Item = Pos> {
+        let (dx, dy) = ((other.x - self.x).abs(), -(other.y - self.y).abs());
+        let step = (other - self).signum();
+        let mut error = dx + dy;
+        let mut next = Some(self);
+
+        std::iter::from_fn(move || {
+            let pos = next?;
+            next = (pos != other).then(|| {
+                let mut after = pos;
+                let doubled = 2 * error;
+                if doubled >= dy {
+                    after.x += step.x;
+                    error += dy;
+                }
+                if doubled <= dx {
+                    after.y += step.y;
+                    error += dx;
+                }
+                after
+            });
+            Some(pos)
+        })
+    }
+
+    /// Only the positions exactly on the straight line to `other`, both ends included. They're a
+    /// `reduced` step apart, so `(0, 0)` to `(2, 3)` has nothing between its ends.
+    pub fn lattice_points_to(self, other: Pos) -> impl Iterator<Item = Pos> {
+        let delta = other - self;
+        let steps = gcd(delta.x.unsigned_abs(), delta.y.unsigned_abs()) as isize;
+        let step = delta.reduced();
+
+        (0..=steps).map(move |n| self + step * n)
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((x, y): (usize, usize)) -> Self {
+        Pos::new(x as isize, y as isize)
+    }
+}
+
+impl From<(isize, isize)> for Pos {
+    fn from((x, y): (isize, isize)) -> Self {
+        Pos::new(x, y)
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, other: Pos) -> Pos {
+        Pos::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Pos {
+    fn add_assign(&mut self, other: Pos) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, other: Pos) -> Pos {
+        Pos::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Pos {
+    fn sub_assign(&mut self, other: Pos) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Pos {
+    type Output = Pos;
+
+    fn mul(self, factor: isize) -> Pos {
+        Pos::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Pos {
+    type Output = Pos;
+
+    fn neg(self) -> Pos {
+        Pos::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_operators() {
+        let pos = Pos::new(2, 3);
+
+        assert_eq!(pos + Pos::UP, Pos::new(1, 3));
+        assert_eq!(pos - Pos::new(5, 1), Pos::new(-3, 2));
+        assert_eq!(Pos::new(1, -2) * 3, Pos::new(3, -6));
+        assert_eq!(-pos, Pos::new(-2, -3));
+
+        let mut moving = pos;
+        moving += Pos::RIGHT;
+        moving -= Pos::DOWN;
+        assert_eq!(moving, Pos::new(1, 4));
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(Pos::new(4, -6).reduced(), Pos::new(2, -3));
+        assert_eq!(Pos::new(0, 5).reduced(), Pos::RIGHT);
+        assert_eq!(Pos::ZERO.reduced(), Pos::ZERO);
+
+        let line: Vec<Pos> = Pos::new(1, 1).lattice_points_to(Pos::new(7, -3)).collect();
+        assert_eq!(line, vec![Pos::new(1, 1), Pos::new(4, -1), Pos::new(7, -3)]);
+        let line: Vec<Pos> = Pos::ZERO.lattice_points_to(Pos::new(2, 3)).collect();
+        assert_eq!(line, vec![Pos::ZERO, Pos::new(2, 3)]);
+        assert_eq!(Pos::new(2, 2).lattice_points_to(Pos::new(2, 2)).collect::<Vec<_>>(), vec![Pos::new(2, 2)]);
+    }
+
+    #[test]
+    fn test_line_to() {
+        let line: Vec<Pos> = Pos::ZERO.line_to(Pos::new(2, 3)).collect();
+        assert_eq!(line, vec![Pos::ZERO, Pos::new(1, 1), Pos::new(1, 2), Pos::new(2, 3)]);
+        let line: Vec<Pos> = Pos::new(1, 1).line_to(Pos::new(1, -2)).collect();
+        assert_eq!(line, vec![Pos::new(1, 1), Pos::new(1, 0), Pos::new(1, -1), Pos::new(1, -2)]);
+        assert_eq!(Pos::new(2, 2).line_to(Pos::new(2, 2)).collect::<Vec<_>>(), vec![Pos::new(2, 2)]);
+
+        // Every direction gets one position per step along the longer axis, each touching the last
+        for end in Pos::new(0, 0).diamond(9) {
+            let line: Vec<Pos> = Pos::new(3, -2).line_to(Pos::new(3, -2) + end).collect();
+            assert_eq!(line.len(), end.x.unsigned_abs().max(end.y.unsigned_abs()) + 1);
+            assert_eq!(line.last(), Some(&(Pos::new(3, -2) + end)));
+            assert!(line.windows(2).all(|pair| pair[0].chebyshev(pair[1]) == 1));
+        }
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Pos::new(1, 1);
+        let b = Pos::new(-2, 5);
+
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!((b - a).signum(), Pos::new(-1, 1));
+    }
+
+    #[test]
+    fn test_to_index() {
+        assert_eq!(Pos::new(3, 0).to_index(), Some((3, 0)));
+        assert_eq!((Pos::new(0, 0) + Pos::UP).to_index(), None);
+        assert_eq!(Pos::new(2, -1).to_index(), None);
+    }
+
+    #[test]
+    fn test_rem_euclid() {
+        assert_eq!(Pos::new(-1, 12).rem_euclid(Pos::new(11, 7)), Pos::new(10, 5));
+    }
+
+    #[test]
+    fn test_ring() {
+        let center = Pos::new(5, 5);
+        let ring: Vec<Pos> = center.ring(2).collect();
+
+        assert_eq!(center.ring(0).collect::<Vec<Pos>>(), vec![center]);
+        assert_eq!(ring.len(), 8);
+        assert_eq!(ring.iter().collect::<HashSet<_>>().len(), 8);
+        assert!(ring.iter().all(|&pos| pos.manhattan(center) == 2));
+
+        let offsets: Vec<(isize, isize)> = ring.iter().map(|&pos| (pos.x - 5, pos.y - 5)).collect();
+        assert_eq!(offsets, [(-2, 0), (-1, 1), (0, 2), (1, 1), (2, 0), (1, -1), (0, -2), (-1, -1)]);
+        assert_eq!(Pos::ZERO.ring(1).collect::<Vec<Pos>>(), [Pos::UP, Pos::RIGHT, Pos::DOWN, Pos::LEFT]);
+    }
+
+    #[test]
+    fn test_diamond() {
+        let center = Pos::new(0, 0);
+        let diamond: HashSet<Pos> = center.diamond(3).collect();
+
+        assert_eq!(diamond.len(), 25);
+        assert_eq!(center.diamond(3).next(), Some(center));
+        assert!(diamond.iter().all(|&pos| pos.manhattan(center) <= 3));
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::HashSet;
+use std::ops::Index;
+
+use crate::{Map, Pos, Topology};
+
+const UNLABELLED: usize = usize::MAX;
+
+/// Which tiles count as touching when growing a region
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Only the tiles above, below, left and right
+    #[default]
+    Four,
+    /// The diagonal tiles as well
+    Eight,
+}
+
+impl Connectivity {
+    fn deltas(self) -> &'static [Pos] {
+        match self {
+            Connectivity::Four => &Pos::CARDINALS,
+            Connectivity::Eight => &Pos::NEIGHBOURS,
+        }
+    }
+}
+
+/// A connected group of tiles, like a garden plot in day 12
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub label: usize,
+    /// In reading order
+    pub tiles: Vec<Pos>,
+    /// Tile edges with another region or the edge of the map on the other side
+    pub perimeter: usize,
+    /// Straight runs of fence along the perimeter, the ones around holes included
+    pub sides: usize,
+    /// The top left and bottom right corners of the box around the tiles, both inclusive
+    pub bounds: (Pos, Pos),
+    /// Whether a tile is on the edge of the map, which means no region encloses this one
+    pub touches_edge: bool,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.tiles.len()
+    }
+}
+
+/// Every region of a map, labelled in reading order of their first tile
+#[derive(Debug, Clone)]
+pub struct Regions {
+    labels: Map<usize>,
+    regions: Vec<Region>,
+}
+
+impl Index<usize> for Regions {
+    type Output = Region;
+
+    fn index(&self, label: usize) -> &Region {
+        &self.regions[label]
+    }
+}
+
+impl Regions {
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    /// The label of the region `pos` is in
+    pub fn label(&self, pos: Pos) -> Option<usize> {
+        self.labels.at_point(pos).copied()
+    }
+
+    pub fn region_at(&self, pos: Pos) -> Option<&Region> {
+        self.label(pos).map(|label| &self.regions[label])
+    }
+
+    /// The region all the way around `label`, so that every way out of it crosses that region.
+    /// Only bounded maps have enclosed regions. The ways out only go up, down, left and right,
+    /// so a diagonal line of tiles is a wall even when the regions have `Connectivity::Eight`.
+    pub fn enclosing(&self, label: usize) -> Option<usize> {
+        let region = &self.regions[label];
+        if region.touches_edge || self.labels.topology != Topology::Bounded {
+            return None;
+        }
+
+        // Anything reachable from just outside the bounding box without crossing the region is
+        // outside it, the rest is in its holes. Since the region doesn't touch the edge of the
+        // map, the box around it is on the map.
+        let (min, max) = (region.bounds.0 - Pos::new(1, 1), region.bounds.1 + Pos::new(1, 1));
+        let in_box = |pos: Pos| (min.x..=max.x).contains(&pos.x) && (min.y..=max.y).contains(&pos.y);
+        let mut outside: HashSet<Pos> = (min.x..=max.x)
+            .flat_map(|x| (min.y..=max.y).map(move |y| Pos::new(x, y)))
+            .filter(|pos| pos.x == min.x || pos.x == max.x || pos.y == min.y || pos.y == max.y)
+            .collect();
+        let mut to_explore: Vec<Pos> = outside.iter().copied().collect();
+
+        while let Some(pos) = to_explore.pop() {
+            for delta in Pos::CARDINALS {
+                let next = pos + delta;
+                if in_box(next) && self.labels[next] != label && outside.insert(next) {
+                    to_explore.push(next);
+                }
+            }
+        }
+
+        let mut around = outside
+            .iter()
+            .filter(|&&pos| Pos::CARDINALS.iter().any(|&delta| self.label(pos + delta) == Some(label)))
+            .map(|&pos| self.labels[pos]);
+        let first = around.next()?;
+
+        around.all(|other| other == first).then_some(first)
+    }
+
+    /// The regions that `label` encloses
+    pub fn enclosed(&self, label: usize) -> Vec<usize> {
+        (0..self.regions.len())
+            .filter(|&other| other != label && self.enclosing(other) == Some(label))
+            .collect()
+    }
+}
+
+impl<T> Map<T> {
+    /// Group the tiles into connected regions, where neighbouring tiles are in the same region
+    /// when `eq` says so. Neighbours follow the map's topology.
+    pub fn regions(&self, connectivity: Connectivity, eq: impl Fn(&T, &T) -> bool) -> Regions {
+        let mut labels = Map::new(self.width, self.height, UNLABELLED).with_topology(self.topology);
+        let mut grouped: Vec<Vec<Pos>> = vec![];
+
+        for (start, _) in self.iter() {
+            if labels[start] != UNLABELLED {
+                continue;
+            }
+
+            let label = grouped.len();
+            let mut tiles = vec![];
+            let mut to_explore = vec![start];
+            labels[start] = label;
+
+            while let Some(pos) = to_explore.pop() {
+                tiles.push(pos);
+                for &delta in connectivity.deltas() {
+                    let Some(next) = self.step(pos, delta) else {
+                        continue;
+                    };
+                    if labels[next] == UNLABELLED && eq(&self[pos], &self[next]) {
+                        labels[next] = label;
+                        to_explore.push(next);
+                    }
+                }
+            }
+
+            tiles.sort();
+            grouped.push(tiles);
+        }
+
+        let regions = grouped
+            .into_iter()
+            .enumerate()
+            .map(|(label, tiles)| describe(&labels, label, tiles))
+            .collect();
+
+        Regions { labels, regions }
+    }
+}
+
+fn describe(labels: &Map<usize>, label: usize, tiles: Vec<Pos>) -> Region {
+    // Whether the edge of `pos` facing `delta` is part of the fence
+    let fenced = |pos: Pos, delta: Pos| labels.step(pos, delta).is_none_or(|next| labels[next] != label);
+
+    // The next tile along the fence facing `delta`, looking `along` it, when the side goes on there
+    let continues = |pos: Pos, delta: Pos, along: Pos| {
+        labels.step(pos, along).filter(|&next| labels[next] == label && fenced(next, delta))
+    };
+
+    let fences: Vec<(Pos, Pos)> = tiles
+        .iter()
+        .flat_map(|&pos| Pos::CARDINALS.into_iter().map(move |delta| (pos, delta)))
+        .filter(|&(pos, delta)| fenced(pos, delta))
+        .collect();
+    let perimeter = fences.len();
+
+    // Count each side once, at the tile where it starts, and follow it to its end. Looking along
+    // the fence, the tile before this one continues the side when it's in the region and fenced
+    // too.
+    let mut sides = 0;
+    let mut counted: HashSet<(Pos, Pos)> = HashSet::new();
+    let follow = |start: Pos, delta: Pos, counted: &mut HashSet<(Pos, Pos)>| {
+        let mut pos = start;
+        while counted.insert((pos, delta)) {
+            match continues(pos, delta, Pos::new(-delta.y, delta.x)) {
+                Some(next) => pos = next,
+                None => break,
+            }
+        }
+    };
+    for &(pos, delta) in &fences {
+        if continues(pos, delta, Pos::new(delta.y, -delta.x)).is_none() {
+            sides += 1;
+            follow(pos, delta, &mut counted);
+        }
+    }
+
+    // On maps that wrap around, a fence can go all the way around without starting anywhere
+    for &(pos, delta) in &fences {
+        if !counted.contains(&(pos, delta)) {
+            sides += 1;
+            follow(pos, delta, &mut counted);
+        }
+    }
+
+    let min = tiles.iter().fold(tiles[0], |min, pos| Pos::new(min.x.min(pos.x), min.y.min(pos.y)));
+    let max = tiles.iter().fold(tiles[0], |max, pos| Pos::new(max.x.max(pos.x), max.y.max(pos.y)));
+    let touches_edge = tiles.iter().any(|&pos| Pos::CARDINALS.iter().any(|&delta| labels.step(pos, delta).is_none()));
+
+    Region {
+        label,
+        tiles,
+        perimeter,
+        sides,
+        bounds: (min, max),
+        touches_edge,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_garden() {
+        let map: Map<char> = Map::from("AAAA\nBBCD\nBBCC\nEEEC");
+        let regions = map.regions(Connectivity::Four, |a, b| a == b);
+
+        assert_eq!(regions.len(), 5);
+        let areas: Vec<usize> = regions.iter().map(Region::area).collect();
+        let perimeters: Vec<usize> = regions.iter().map(|region| region.perimeter).collect();
+        let sides: Vec<usize> = regions.iter().map(|region| region.sides).collect();
+        assert_eq!(areas, [4, 4, 4, 1, 3]);
+        assert_eq!(perimeters, [10, 8, 10, 4, 8]);
+        assert_eq!(sides, [4, 4, 8, 4, 4]);
+
+        let c = regions.region_at(Pos::new(1, 2)).unwrap();
+        assert_eq!(c.label, 2);
+        assert_eq!(c.bounds, (Pos::new(1, 2), Pos::new(3, 3)));
+    }
+
+    #[test]
+    fn test_enclosing() {
+        let map: Map<char> = Map::from("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n");
+        let regions = map.regions(Connectivity::Four, |a, b| a == b);
+
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions[0].sides, 12);
+        assert_eq!(regions.enclosing(1), Some(0));
+        assert_eq!(regions.enclosing(0), None);
+        assert_eq!(regions.enclosed(0), [1, 2]);
+
+        // With diagonals the two B regions touch and become one
+        let regions = map.regions(Connectivity::Eight, |a, b| a == b);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[1].area(), 8);
+        assert_eq!(regions.enclosed(0), [1]);
+    }
+
+    #[test]
+    fn test_holes_in_holes() {
+        let map: Map<char> = Map::from(".......\n.#####.\n.#...#.\n.#.o.#.\n.#...#.\n.#####.\n.......");
+        let regions = map.regions(Connectivity::Four, |a, b| a == b);
+        let wall = regions.label(Pos::new(1, 1)).unwrap();
+        let inside = regions.label(Pos::new(2, 2)).unwrap();
+        let pebble = regions.label(Pos::new(3, 3)).unwrap();
+
+        assert_eq!(regions.len(), 4);
+        assert_eq!(regions.enclosing(pebble), Some(inside));
+        assert_eq!(regions.enclosing(inside), Some(wall));
+        assert_eq!(regions.enclosing(wall), Some(0));
+        assert_eq!(regions.enclosed(wall), [inside]);
+        assert_eq!(regions[wall].sides, 8);
+    }
+
+    #[test]
+    fn test_diagonal_walls() {
+        let map: Map<char> = Map::from(".....\n..#..\n.#o#.\n..#..\n.....");
+        let regions = map.regions(Connectivity::Eight, |a, b| a == b);
+        let wall = regions.label(Pos::new(1, 2)).unwrap();
+        let inside = regions.label(Pos::new(2, 2)).unwrap();
+
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions.enclosing(inside), Some(wall));
+        assert_eq!(regions.enclosed(wall), [inside]);
+    }
+
+    #[test]
+    fn test_sides_around_a_torus() {
+        let map: Map<char> = Map::from("aa\nbb").with_topology(Topology::Toroidal);
+        let regions = map.regions(Connectivity::Four, |a, b| a == b);
+
+        // The fences above and below each row go all the way around, one side each
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].perimeter, 4);
+        assert_eq!(regions[0].sides, 2);
+        assert_eq!(regions[1].sides, 2);
+
+        let map: Map<char> = Map::from("aa\nbb");
+        assert_eq!(map.regions(Connectivity::Four, |a, b| a == b)[0].sides, 4);
+    }
+}
@@ -0,0 +1,293 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{Map, Pos};
+
+mod gif;
+mod png;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(40, 160, 60);
+    pub const BLUE: Rgb = Rgb(38, 110, 210);
+    pub const YELLOW: Rgb = Rgb(240, 200, 40);
+}
+
+/// An RGB picture addressed like a `Map`: `x` is the pixel row and `y` the column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// One pixel per tile, coloured by `colour`
+    pub fn from_map<T>(map: &Map<T>, mut colour: impl FnMut(&T) -> Rgb) -> Self {
+        Image {
+            width: map.width,
+            height: map.height,
+            pixels: map.iter().map(|(_, tile)| colour(tile)).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<Rgb> {
+        let (x, y) = pos.to_index()?;
+        (x < self.height && y < self.width).then(|| self.pixels[x * self.width + y])
+    }
+
+    /// Pixels outside the image are skipped, so overlays don't need to be clipped first
+    pub fn set(&mut self, pos: Pos, colour: Rgb) {
+        if let Some((x, y)) = pos.to_index().filter(|&(x, y)| x < self.height && y < self.width) {
+            self.pixels[x * self.width + y] = colour;
+        }
+    }
+
+    pub fn draw_points(&mut self, points: impl IntoIterator<Item = Pos>, colour: Rgb) {
+        for point in points {
+            self.set(point, colour);
+        }
+    }
+
+    /// Draw straight lines between consecutive points, so a path can skip the steps in between
+    pub fn draw_path(&mut self, path: &[Pos], colour: Rgb) {
+        if let [single] = path {
+            self.set(*single, colour);
+        }
+
+        for pair in path.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let steps = from.chebyshev(to).max(1);
+            let delta = to - from;
+
+            for step in 0..=steps {
+                let t = step as f64 / steps as f64;
+                let x = from.x + (delta.x as f64 * t).round() as isize;
+                let y = from.y + (delta.y as f64 * t).round() as isize;
+                self.set(Pos::new(x, y), colour);
+            }
+        }
+    }
+
+    /// Blow every pixel up to a `factor` by `factor` square, small maps are hard to see otherwise
+    pub fn scale(&self, factor: usize) -> Image {
+        let width = self.width * factor;
+        let pixels = self
+            .pixels
+            .chunks(self.width.max(1))
+            .flat_map(|row| {
+                let scaled_row: Vec<Rgb> = row
+                    .iter()
+                    .flat_map(|&pixel| std::iter::repeat_n(pixel, factor))
+                    .collect();
+                std::iter::repeat_n(scaled_row, factor).flatten()
+            })
+            .collect();
+
+        Image {
+            width,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    /// Binary PPM (P6), the simplest format most image viewers open
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in &self.pixels {
+            ppm.extend([pixel.0, pixel.1, pixel.2]);
+        }
+        ppm
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        png::encode(self.width, self.height, &self.pixels)
+    }
+
+    /// Write a `.png` or `.ppm` file, depending on the extension of `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Can't tell the image format of {}, use .png or .ppm", path.display()),
+                ))
+            }
+        };
+
+        fs::write(path, bytes)
+    }
+}
+
+/// Frames of a simulation, saved as an animated GIF or as numbered images
+#[derive(Debug, Clone)]
+pub struct Animation {
+    frames: Vec<Image>,
+    delay: u16,
+}
+
+impl Animation {
+    /// `delay` is how long every frame is shown, in hundredths of a second like GIF stores it
+    pub fn new(delay: u16) -> Self {
+        Animation { frames: vec![], delay }
+    }
+
+    /// Panics when the frame isn't the same size as the first one
+    pub fn push(&mut self, frame: Image) {
+        if let Some(first) = self.frames.first() {
+            assert!(
+                (first.width, first.height) == (frame.width, frame.height),
+                "Frames must all be {}x{}",
+                first.height,
+                first.width
+            );
+        }
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Fails when the frames use more than 256 colours between them, or are too big for a GIF
+    pub fn to_gif(&self) -> io::Result<Vec<u8>> {
+        gif::encode(&self.frames, self.delay)
+    }
+
+    pub fn save_gif(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_gif()?)
+    }
+
+    /// Write every frame to `directory/frame_0000.<extension>`, `frame_0001.<extension>`, ...
+    pub fn save_frames(&self, directory: impl AsRef<Path>, extension: &str) -> io::Result<Vec<PathBuf>> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+
+        self.frames
+            .iter()
+            .enumerate()
+            .map(|(index, frame)| {
+                let path = directory.join(format!("frame_{:04}.{}", index, extension));
+                frame.save(&path)?;
+                Ok(path)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> Image {
+        let map: Map<char> = Map::from("#.\n.#\n..");
+        Image::from_map(&map, |&tile| if tile == '#' { Rgb::BLACK } else { Rgb::WHITE })
+    }
+
+    #[test]
+    fn test_from_map() {
+        let image = image();
+
+        assert_eq!((image.width(), image.height()), (2, 3));
+        assert_eq!(image.get(Pos::new(1, 1)), Some(Rgb::BLACK));
+        assert_eq!(image.get(Pos::new(2, 0)), Some(Rgb::WHITE));
+        assert_eq!(image.get(Pos::new(3, 0)), None);
+    }
+
+    #[test]
+    fn test_draw_path() {
+        let mut image = Image::new(5, 5, Rgb::BLACK);
+        image.draw_path(&[Pos::new(0, 0), Pos::new(0, 4), Pos::new(4, 0)], Rgb::RED);
+        image.draw_points([Pos::new(-1, 0), Pos::new(4, 4)], Rgb::BLUE);
+
+        let red: Vec<Pos> = (0..5)
+            .flat_map(|x| (0..5).map(move |y| Pos::new(x, y)))
+            .filter(|&pos| image.get(pos) == Some(Rgb::RED))
+            .collect();
+        // The top row, then the diagonal back to the bottom left corner
+        assert_eq!(red.len(), 5 + 4);
+        assert!(red.contains(&Pos::new(2, 2)));
+        assert_eq!(image.get(Pos::new(4, 4)), Some(Rgb::BLUE));
+    }
+
+    #[test]
+    fn test_scale() {
+        let scaled = image().scale(3);
+
+        assert_eq!((scaled.width(), scaled.height()), (6, 9));
+        assert_eq!(scaled.get(Pos::new(2, 2)), Some(Rgb::BLACK));
+        assert_eq!(scaled.get(Pos::new(2, 3)), Some(Rgb::WHITE));
+        assert_eq!(scaled.get(Pos::new(5, 5)), Some(Rgb::BLACK));
+    }
+
+    #[test]
+    fn test_ppm() {
+        let ppm = image().to_ppm();
+
+        assert!(ppm.starts_with(b"P6\n2 3\n255\n"));
+        assert_eq!(ppm.len(), 11 + 2 * 3 * 3);
+        assert_eq!(&ppm[11..14], &[0, 0, 0]);
+    }
+
+    #[test]
+    fn test_save_checks_extension() {
+        assert!(image().save("image.bmp").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_animation_frame_sizes() {
+        let mut animation = Animation::new(10);
+        animation.push(Image::new(2, 2, Rgb::BLACK));
+        animation.push(Image::new(3, 2, Rgb::BLACK));
+    }
+
+    #[test]
+    fn test_save_frames() {
+        let directory = std::env::temp_dir().join(format!("aoc-render-{}", std::process::id()));
+        let mut animation = Animation::new(10);
+        animation.push(image());
+        animation.push(image().scale(1));
+
+        let paths = animation.save_frames(&directory, "ppm").unwrap();
+
+        assert_eq!(paths.len(), 2);
+        assert!(paths[1].ends_with("frame_0001.ppm"));
+        assert_eq!(fs::read(&paths[0]).unwrap(), image().to_ppm());
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io;
+
+use super::{Image, Rgb};
+
+const MAX_CODES: u16 = 4096;
+
+/// Animated GIF89a that loops forever, with one global palette shared by all frames
+pub(super) fn encode(frames: &[Image], delay: u16) -> io::Result<Vec<u8>> {
+    let Some(first) = frames.first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "An animation needs at least one frame"));
+    };
+    let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "GIF images can be at most 65535 pixels wide or high");
+    let width = u16::try_from(first.width).map_err(|_| too_big())?;
+    let height = u16::try_from(first.height).map_err(|_| too_big())?;
+
+    let mut palette: Vec<Rgb> = vec![];
+    let mut palette_index: HashMap<Rgb, u8> = HashMap::new();
+    for &pixel in frames.iter().flat_map(|frame| &frame.pixels) {
+        if let Entry::Vacant(entry) = palette_index.entry(pixel) {
+            if palette.len() == 256 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "GIF frames can use at most 256 colours"));
+            }
+            entry.insert(palette.len() as u8);
+            palette.push(pixel);
+        }
+    }
+
+    // The colour table has 2^bits entries, from 2 up to 256
+    let bits = (1..=8).find(|&bits| palette.len() <= 1 << bits).unwrap();
+    palette.resize(1 << bits, Rgb::BLACK);
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(width.to_le_bytes());
+    gif.extend(height.to_le_bytes());
+    gif.extend([0x80 | ((bits - 1) << 4) | (bits - 1), 0, 0]);
+    for colour in &palette {
+        gif.extend([colour.0, colour.1, colour.2]);
+    }
+
+    if frames.len() > 1 {
+        // Application extension that makes viewers loop the animation
+        gif.extend([0x21, 0xff, 0x0b]);
+        gif.extend(b"NETSCAPE2.0");
+        gif.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+    }
+
+    let min_code_size = bits.max(2);
+    for frame in frames {
+        // Graphic control extension, only used for the delay
+        gif.extend([0x21, 0xf9, 0x04, 0x00]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+
+        // Image descriptor covering the whole screen, without a local palette
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend(width.to_le_bytes());
+        gif.extend(height.to_le_bytes());
+        gif.push(0x00);
+
+        let indexes: Vec<u8> = frame.pixels.iter().map(|pixel| palette_index[pixel]).collect();
+        gif.push(min_code_size);
+        for block in lzw(&indexes, min_code_size).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0x00);
+    }
+
+    gif.push(0x3b);
+    Ok(gif)
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    length: u8,
+}
+
+impl BitWriter {
+    // GIF packs codes starting from the least significant bit
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.length;
+        self.length += size;
+        while self.length >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.length -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.length > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn lzw(indexes: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter::default();
+    let mut code_size = min_code_size + 1;
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = end + 1;
+
+    writer.write(clear, code_size);
+
+    let Some((&first, rest)) = indexes.split_first() else {
+        writer.write(end, code_size);
+        return writer.finish();
+    };
+
+    let mut current = first as u16;
+    for &index in rest {
+        if let Some(&code) = codes.get(&(current, index)) {
+            current = code;
+            continue;
+        }
+
+        writer.write(current, code_size);
+        if next_code < MAX_CODES {
+            codes.insert((current, index), next_code);
+            next_code += 1;
+            // The decoder adds its entries one code later, so it widens once it's past this size
+            if next_code > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            writer.write(clear, code_size);
+            codes.clear();
+            next_code = end + 1;
+            code_size = min_code_size + 1;
+        }
+        current = index as u16;
+    }
+
+    writer.write(current, code_size);
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A straightforward decoder, to check the encoder against
+    fn unlzw(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let reset = || -> Vec<Vec<u8>> { (0..clear).map(|i| vec![i as u8]).chain([vec![], vec![]]).collect() };
+
+        let mut table = reset();
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = vec![];
+        let mut bit = 0;
+
+        loop {
+            let code = (0..code_size as usize).fold(0, |code, i| {
+                let position = bit + i;
+                code | (((data[position / 8] >> (position % 8)) & 1) as usize) << i
+            });
+            bit += code_size as usize;
+
+            if code == clear {
+                table = reset();
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return output;
+            }
+
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.as_slice(), &previous[..1]].concat(),
+                (None, None) => panic!("Unknown code {}", code),
+            };
+            if let Some(previous) = previous {
+                if table.len() < MAX_CODES as usize {
+                    table.push([previous.as_slice(), &entry[..1]].concat());
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                }
+            }
+
+            output.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        // Enough noise to fill the code table a few times over
+        let mut seed = 12345u32;
+        let indexes: Vec<u8> = (0..50_000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                ((seed >> 16) % 13) as u8
+            })
+            .collect();
+
+        assert_eq!(unlzw(&lzw(&indexes, 4), 4), indexes);
+        assert_eq!(unlzw(&lzw(&[1; 10_000], 2), 2), vec![1; 10_000]);
+        assert_eq!(unlzw(&lzw(&[], 2), 2), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_encode() {
+        let frames = [Image::new(3, 2, Rgb::RED), Image::new(3, 2, Rgb::BLUE)];
+        let gif = encode(&frames, 20).unwrap();
+
+        assert!(gif.starts_with(b"GIF89a\x03\x00\x02\x00"));
+        assert!(gif.windows(11).any(|window| window == b"NETSCAPE2.0"));
+        let descriptor = [0x2c, 0, 0, 0, 0, 3, 0, 2, 0, 0];
+        assert_eq!(gif.windows(descriptor.len()).filter(|&window| window == descriptor).count(), 2);
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn test_too_many_colours() {
+        let mut frame = Image::new(300, 1, Rgb::BLACK);
+        for y in 0..300 {
+            frame.set(crate::Pos::new(0, y), Rgb(y as u8, (y / 256) as u8, 0));
+        }
+
+        assert!(encode(&[frame], 10).is_err());
+        assert!(encode(&[], 10).is_err());
+    }
+}
@@ -0,0 +1,420 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::{Map, Pos};
+
+/// Outcome of a search: the cheapest cost of every state that was reached, and every
+/// predecessor that reaches a state at that cost.
+///
+/// Once a goal is found the search keeps going until all states at the goal's cost are
+/// settled, so `predecessors` covers every shortest path to every goal.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    costs: HashMap<S, C>,
+    predecessors: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+impl<S, C> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy,
+{
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// Every reached state with its cheapest cost
+    pub fn costs(&self) -> impl Iterator<Item = (&S, &C)> {
+        self.costs.iter()
+    }
+
+    /// The first goal that was reached, if any
+    pub fn goal(&self) -> Option<&S> {
+        self.goals.first()
+    }
+
+    /// All goals that were reached at the cheapest cost
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal().and_then(|goal| self.cost(goal))
+    }
+
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// One of the cheapest paths from a start to `state`, both included
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.costs.get(state)?;
+
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every state that's on at least one cheapest path to `state`, including `state` itself
+    pub fn states_on_paths_to(&self, state: &S) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        if !self.costs.contains_key(state) {
+            return seen;
+        }
+
+        let mut queue = vec![state.clone()];
+        while let Some(state) = queue.pop() {
+            if seen.insert(state.clone()) {
+                queue.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+
+        seen
+    }
+
+    /// Number of distinct cheapest paths from any start to `state`
+    pub fn count_paths(&self, state: &S) -> u64 {
+        fn count<S: Clone + Eq + Hash, C: Copy>(search: &Search<S, C>, state: &S, cache: &mut HashMap<S, u64>) -> u64 {
+            if let Some(&paths) = cache.get(state) {
+                return paths;
+            }
+
+            let predecessors = search.predecessors(state);
+            let paths = if predecessors.is_empty() {
+                1
+            } else {
+                predecessors.iter().map(|previous| count(search, previous, cache)).sum()
+            };
+
+            cache.insert(state.clone(), paths);
+            paths
+        }
+
+        if !self.costs.contains_key(state) {
+            return 0;
+        }
+
+        count(self, state, &mut HashMap::new())
+    }
+}
+
+/// Breadth first search where every step costs 1. `is_goal` may always return `false` to
+/// explore everything that's reachable.
+pub fn bfs<S, I, N, G>(starts: impl IntoIterator<Item = S>, mut neighbours: N, mut is_goal: G) -> Search<S, u32>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    let mut search = Search {
+        costs: HashMap::new(),
+        predecessors: HashMap::new(),
+        goals: vec![],
+    };
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !search.costs.contains_key(&start) {
+            search.costs.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, cost)) = queue.pop_front() {
+        if search.goal_cost().is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+
+        if is_goal(&state) {
+            search.goals.push(state.clone());
+            continue;
+        }
+
+        for next in neighbours(&state) {
+            match search.costs.get(&next) {
+                None => {
+                    search.costs.insert(next.clone(), cost + 1);
+                    search.predecessors.insert(next.clone(), vec![state.clone()]);
+                    queue.push_back((next, cost + 1));
+                }
+                Some(&existing) if existing == cost + 1 => {
+                    // `neighbours` can give the same state twice, like up and down on a torus 2 high
+                    let predecessors = search.predecessors.entry(next).or_default();
+                    if !predecessors.contains(&state) {
+                        predecessors.push(state.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    search
+}
+
+/// Cheapest path search over weighted edges. `neighbours` returns `(state, cost)` pairs.
+pub fn dijkstra<S, C, I, N, G>(starts: impl IntoIterator<Item = S>, neighbours: N, is_goal: G) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+    N: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// Dijkstra guided by a `heuristic`, which must never overestimate the remaining cost. A state
+/// that was already expanded is expanded again when a cheaper path to it turns up, so the costs
+/// are the cheapest even when the heuristic isn't consistent.
+pub fn astar<S, C, I, N, H, G>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: N,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+    N: FnMut(&S) -> I,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut search = Search {
+        costs: HashMap::new(),
+        predecessors: HashMap::new(),
+        goals: vec![],
+    };
+    let mut settled: HashSet<S> = HashSet::new();
+
+    // The heap only holds indexes, so states don't need to be `Ord`
+    let mut states: Vec<S> = vec![];
+    let mut queue: BinaryHeap<Reverse<(C, C, usize)>> = BinaryHeap::new();
+
+    for start in starts {
+        if !search.costs.contains_key(&start) {
+            search.costs.insert(start.clone(), C::default());
+            queue.push(Reverse((heuristic(&start), C::default(), states.len())));
+            states.push(start);
+        }
+    }
+
+    while let Some(Reverse((estimate, cost, index))) = queue.pop() {
+        if search.goal_cost().is_some_and(|goal_cost| estimate > goal_cost) {
+            break;
+        }
+
+        let state = states[index].clone();
+        if search.costs[&state] < cost || !settled.insert(state.clone()) {
+            continue;
+        }
+
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+
+        for (next, step_cost) in neighbours(&state) {
+            let next_cost = cost + step_cost;
+
+            match search.costs.get(&next) {
+                Some(&existing) if existing < next_cost => {}
+                Some(&existing) if existing == next_cost => {
+                    let predecessors = search.predecessors.entry(next).or_default();
+                    if !predecessors.contains(&state) {
+                        predecessors.push(state.clone());
+                    }
+                }
+                _ => {
+                    settled.remove(&next);
+                    search.costs.insert(next.clone(), next_cost);
+                    search.predecessors.insert(next.clone(), vec![state.clone()]);
+                    queue.push(Reverse((next_cost + heuristic(&next), next_cost, states.len())));
+                    states.push(next);
+                }
+            }
+        }
+    }
+
+    search
+}
+
+/// Number of steps from the nearest of `sources` to every tile, moving between cardinal
+/// neighbours that are `passable`. Unreachable tiles are `None`.
+pub fn distance_field<T, P>(map: &Map<T>, sources: impl IntoIterator<Item = Pos>, mut passable: P) -> Map<Option<u32>>
+where
+    P: FnMut(Pos, &T) -> bool,
+{
+    let search = bfs(
+        sources,
+        |&pos| {
+            map.cardinal_neighbours(pos)
+                .filter_map(|(next, tile)| passable(next, tile).then_some(next))
+                .collect::<Vec<_>>()
+        },
+        |_| false,
+    );
+
+    let mut distances = Map::new(map.width, map.height, None);
+    for (&pos, &cost) in search.costs() {
+        distances.set_point(pos, Some(cost));
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Topology;
+
+    const MAZE: &str = "\
+.....
+.###.
+.....
+.#.#.
+.....";
+
+    fn open_neighbours(map: &Map<char>, pos: Pos) -> Vec<Pos> {
+        map.cardinal_neighbours(pos)
+            .filter_map(|(next, &tile)| (tile == '.').then_some(next))
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs_shortest_path() {
+        let map: Map<char> = Map::from(MAZE);
+        let goal = Pos::new(4, 4);
+        let search = bfs([Pos::ZERO], |&pos| open_neighbours(&map, pos), |&pos| pos == goal);
+
+        assert_eq!(search.goal(), Some(&goal));
+        assert_eq!(search.goal_cost(), Some(8));
+
+        let path = search.path_to(&goal).unwrap();
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.first(), Some(&Pos::ZERO));
+        assert_eq!(path.last(), Some(&goal));
+    }
+
+    #[test]
+    fn test_bfs_all_shortest_paths() {
+        let map: Map<char> = Map::from(MAZE);
+        let goal = Pos::new(4, 4);
+        let search = bfs([Pos::ZERO], |&pos| open_neighbours(&map, pos), |&pos| pos == goal);
+
+        // Down the right edge, or down the left edge and through one of the gaps in row 3
+        assert_eq!(search.count_paths(&goal), 4);
+        assert_eq!(search.states_on_paths_to(&goal).len(), 20);
+        assert_eq!(search.predecessors(&goal).len(), 2);
+    }
+
+    #[test]
+    fn test_bfs_same_neighbour_twice() {
+        let map: Map<char> = Map::from(".\n.").with_topology(Topology::Toroidal);
+        let goal = Pos::new(1, 0);
+        let search = bfs([Pos::ZERO], |&pos| open_neighbours(&map, pos), |&pos| pos == goal);
+
+        // Up and down both lead to the goal, but it's the same step
+        assert_eq!(search.predecessors(&goal), [Pos::ZERO]);
+        assert_eq!(search.count_paths(&goal), 1);
+
+        let search = dijkstra([0u32], |&n| [(n + 1, 1u32), (n + 1, 1)], |&n| n == 3);
+        assert_eq!(search.count_paths(&3), 1);
+    }
+
+    #[test]
+    fn test_bfs_unreachable() {
+        let map: Map<char> = Map::from("..#.");
+        let goal = Pos::new(0, 3);
+        let search = bfs([Pos::ZERO], |&pos| open_neighbours(&map, pos), |&pos| pos == goal);
+
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.path_to(&goal), None);
+        assert_eq!(search.count_paths(&goal), 0);
+        assert_eq!(search.cost(&Pos::new(0, 1)), Some(1));
+    }
+
+    #[test]
+    fn test_dijkstra_prefers_cheap_edges() {
+        // a -> b -> d costs 2, a -> d costs 5, a -> c -> d costs 2 too
+        let edges: HashMap<char, Vec<(char, u32)>> = [
+            ('a', vec![('b', 1), ('c', 1), ('d', 5)]),
+            ('b', vec![('d', 1)]),
+            ('c', vec![('d', 1)]),
+        ]
+        .into();
+        let search = dijkstra(['a'], |state| edges.get(state).cloned().unwrap_or_default(), |&state| state == 'd');
+
+        assert_eq!(search.goal_cost(), Some(2));
+        assert_eq!(search.count_paths(&'d'), 2);
+
+        let mut predecessors = search.predecessors(&'d').to_vec();
+        predecessors.sort();
+        assert_eq!(predecessors, vec!['b', 'c']);
+    }
+
+    #[test]
+    fn test_dijkstra_multiple_goals() {
+        let search = dijkstra([0u32], |&n| [(n + 2, 1u32), (n + 3, 1)], |&n| n >= 4);
+
+        assert_eq!(search.goal_cost(), Some(2));
+        // 4, 5 and 6 are all two steps away
+        let mut goals = search.goals().to_vec();
+        goals.sort();
+        assert_eq!(goals, vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn test_astar_matches_bfs() {
+        let map: Map<char> = Map::from(MAZE);
+        let goal = Pos::new(4, 4);
+        let search = astar(
+            [Pos::ZERO],
+            |&pos| open_neighbours(&map, pos).into_iter().map(|next| (next, 1u32)),
+            |&pos| pos.manhattan(goal) as u32,
+            |&pos| pos == goal,
+        );
+
+        assert_eq!(search.goal_cost(), Some(8));
+        assert_eq!(search.count_paths(&goal), 4);
+    }
+
+    #[test]
+    fn test_astar_inconsistent_heuristic() {
+        // s -> a -> c -> g costs 5 and s -> c -> g costs 6. The heuristic is right about a, but
+        // that's more than the 1 it takes to get to c, which makes c come out first from s.
+        let edges: HashMap<char, Vec<(char, u32)>> =
+            [('s', vec![('a', 1), ('c', 3)]), ('a', vec![('c', 1)]), ('c', vec![('g', 3)])].into();
+        let search = astar(
+            ['s'],
+            |state| edges.get(state).cloned().unwrap_or_default(),
+            |&state| if state == 'a' { 4 } else { 0 },
+            |&state| state == 'g',
+        );
+
+        assert_eq!(search.goal_cost(), Some(5));
+        assert_eq!(search.path_to(&'g'), Some(vec!['s', 'a', 'c', 'g']));
+    }
+
+    #[test]
+    fn test_distance_field() {
+        let map: Map<char> = Map::from("...\n.#.\n..#");
+        let distances = distance_field(&map, [Pos::ZERO, Pos::new(2, 1)], |_, &tile| tile == '.');
+
+        assert_eq!(distances.at(0, 0), Some(&Some(0)));
+        assert_eq!(distances.at(0, 2), Some(&Some(2)));
+        assert_eq!(distances.at(1, 2), Some(&Some(3)));
+        assert_eq!(distances.at(2, 0), Some(&Some(1)));
+        assert_eq!(distances.at(1, 1), Some(&None));
+        assert_eq!(distances.at(2, 2), Some(&None));
+    }
+}
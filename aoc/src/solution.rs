@@ -0,0 +1,222 @@
+use std::fmt;
+
+use crate::bench::{self, Measurement, Settings};
+use crate::gen::{self, CheckError, Report, Rng};
+use crate::viz::Frame;
+use crate::Params;
+
+/// A single day's puzzle. The input is parsed once and both parts are answered from it.
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Input;
+    type Part1: fmt::Display;
+    type Part2: fmt::Display;
+
+    fn parse(input: &str) -> Self::Input;
+
+    /// Parse the input with settings from an example's sidecar, see `Params`. Days whose examples
+    /// don't change any settings keep this default.
+    fn parse_with(input: &str, _params: &Params) -> Self::Input {
+        Self::parse(input)
+    }
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// Replay the puzzle step by step for `aoc watch`, passing every step's frame to `show`.
+    /// Days without a simulation keep this default.
+    fn watch(_input: &Self::Input, _show: &mut dyn FnMut(&Frame)) -> Result<(), String> {
+        Err(format!("Day {} has nothing to watch", Self::DAY))
+    }
+
+    /// A random valid input for property tests, roughly `size` big. Days without a generator
+    /// keep this default.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// A slow but obviously correct answer to compare the real solution with, or `None` when
+    /// there's no reference for `part`
+    fn reference(_input: &str, _part: Part) -> Option<String> {
+        None
+    }
+
+    /// Smaller versions of a failing input, tried in order while shrinking a counterexample
+    fn shrink(input: &str) -> Vec<String> {
+        gen::shrink_lines(input)
+    }
+}
+
+/// Answer for a part that hasn't been solved yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unsolved")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl TryFrom<&str> for Part {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Invalid part: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The answers of one day, formatted the same way by every binary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub day: u8,
+    pub title: &'static str,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Day {:02}: {}", self.day, self.title)?;
+        if let Some(answer) = &self.part1 {
+            writeln!(f, "  Part 1: {}", answer)?;
+        }
+        if let Some(answer) = &self.part2 {
+            writeln!(f, "  Part 2: {}", answer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Object safe view on a `Solution`, so the runner can keep every day in one list
+pub trait Day {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn solve(&self, input: &str, params: &Params, part: Option<Part>) -> Answers;
+    fn watch(&self, input: &str, params: &Params, show: &mut dyn FnMut(&Frame)) -> Result<(), String>;
+    fn bench(&self, input: &str, params: &Params, part: Option<Part>, settings: &Settings) -> Vec<Measurement>;
+    fn check(&self, part: Option<Part>, settings: &gen::Settings) -> Result<Report, CheckError>;
+}
+
+impl<S: Solution> Day for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn solve(&self, input: &str, params: &Params, part: Option<Part>) -> Answers {
+        solve_with::<S>(input, params, part)
+    }
+
+    fn watch(&self, input: &str, params: &Params, show: &mut dyn FnMut(&Frame)) -> Result<(), String> {
+        S::watch(&S::parse_with(input, params), show)
+    }
+
+    fn bench(&self, input: &str, params: &Params, part: Option<Part>, settings: &Settings) -> Vec<Measurement> {
+        bench::bench::<S>(input, params, part, settings)
+    }
+
+    fn check(&self, part: Option<Part>, settings: &gen::Settings) -> Result<Report, CheckError> {
+        gen::check::<S>(part, settings)
+    }
+}
+
+/// Parse the input and answer the requested part, or both parts when `part` is `None`
+pub fn solve<S: Solution>(input: &str, part: Option<Part>) -> Answers {
+    solve_with::<S>(input, &Params::default(), part)
+}
+
+/// Like `solve`, with the settings an example changes
+pub fn solve_with<S: Solution>(input: &str, params: &Params, part: Option<Part>) -> Answers {
+    let input = S::parse_with(input, params);
+
+    Answers {
+        day: S::DAY,
+        title: S::TITLE,
+        part1: (part != Some(Part::Two)).then(|| S::part1(&input).to_string()),
+        part2: (part != Some(Part::One)).then(|| S::part2(&input).to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+        const TITLE: &'static str = "Sum";
+
+        type Input = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = Unsolved;
+
+        fn parse(input: &str) -> Self::Input {
+            input.lines().map(|line| line.parse().unwrap()).collect()
+        }
+
+        fn part1(input: &Self::Input) -> Self::Part1 {
+            input.iter().sum()
+        }
+
+        fn part2(_: &Self::Input) -> Self::Part2 {
+            Unsolved
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let answers = Sum.solve("1\n2\n3", &Params::default(), None);
+
+        assert_eq!(answers.part1, Some("6".to_string()));
+        assert_eq!(answers.part2, Some("unsolved".to_string()));
+        assert_eq!(answers.to_string(), "Day 01: Sum\n  Part 1: 6\n  Part 2: unsolved\n");
+    }
+
+    #[test]
+    fn test_solve_single_part() {
+        let answers = Sum.solve("1\n2\n3", &Params::default(), Some(Part::Two));
+
+        assert_eq!(answers.part1, None);
+        assert_eq!(answers.part2, Some("unsolved".to_string()));
+    }
+
+    #[test]
+    fn test_bench_single_part() {
+        let settings = Settings { warmup: 0, iterations: 1 };
+        let measurements = Sum.bench("1\n2", &Params::default(), Some(Part::One), &settings);
+        let stages: Vec<_> = measurements.iter().map(|measurement| measurement.stage).collect();
+
+        assert_eq!(stages, [bench::Stage::Parse, bench::Stage::Part1]);
+    }
+
+    #[test]
+    fn test_nothing_to_watch() {
+        let error = Sum.watch("1", &Params::default(), &mut |_| {}).unwrap_err();
+
+        assert_eq!(error, "Day 1 has nothing to watch");
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::{Map, ParseMapError, Pos};
+
+/// A grid that only stores the tiles that are set, at any position including negative ones.
+///
+/// Uses the same axes as `Map`. The bounding box grows to fit every tile that's set, but doesn't
+/// shrink again when tiles are removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMap<T> {
+    tiles: HashMap<Pos, T>,
+    bounds: Option<(Pos, Pos)>,
+}
+
+impl<T> Default for SparseMap<T> {
+    fn default() -> Self {
+        SparseMap {
+            tiles: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+/// Empty positions are drawn as `.`
+impl<T> fmt::Display for SparseMap<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                match self.tiles.get(&Pos::new(x, y)) {
+                    Some(tile) => write!(f, "{}", tile)?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<Pos> for SparseMap<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.at_point(pos).unwrap_or_else(|| panic!("There's no tile at {}", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for SparseMap<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos).unwrap_or_else(|| panic!("There's no tile at {}", pos))
+    }
+}
+
+/// Every tile of the map, at the same positions
+impl<T: Clone> From<&Map<T>> for SparseMap<T> {
+    fn from(map: &Map<T>) -> Self {
+        map.iter().map(|(pos, tile)| (pos, tile.clone())).collect()
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        let mut map = SparseMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<T> Extend<(Pos, T)> for SparseMap<T> {
+    fn extend<I: IntoIterator<Item = (Pos, T)>>(&mut self, iter: I) {
+        for (pos, tile) in iter {
+            self.set_point(pos, tile);
+        }
+    }
+}
+
+impl<T> SparseMap<T> {
+    pub fn new() -> Self {
+        SparseMap::default()
+    }
+
+    /// Parse one tile per character like `Map::try_parse`, leaving out the `background` ones
+    pub fn try_parse(input: &str, background: char) -> Result<Self, ParseMapError>
+    where
+        T: TryFrom<char>,
+    {
+        let mut map = SparseMap::new();
+
+        for (x, line) in input.lines().enumerate() {
+            for (y, c) in line.chars().enumerate().filter(|&(_, c)| c != background) {
+                let tile = T::try_from(c).map_err(|_| ParseMapError::InvalidTile {
+                    tile: c,
+                    line: x + 1,
+                    column: y + 1,
+                })?;
+                map.set_point(Pos::from((x, y)), tile);
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// The top left and bottom right corners of the box around every tile, both inclusive
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        self.bounds
+    }
+
+    /// Rows in the bounding box
+    pub fn height(&self) -> usize {
+        self.bounds.map_or(0, |(min, max)| min.x.abs_diff(max.x) + 1)
+    }
+
+    /// Columns in the bounding box
+    pub fn width(&self) -> usize {
+        self.bounds.map_or(0, |(min, max)| min.y.abs_diff(max.y) + 1)
+    }
+
+    pub fn in_bounds(&self, pos: Pos) -> bool {
+        self.bounds
+            .is_some_and(|(min, max)| (min.x..=max.x).contains(&pos.x) && (min.y..=max.y).contains(&pos.y))
+    }
+
+    /// The tile at `pos`, or `None` when it isn't set
+    pub fn at_point(&self, pos: Pos) -> Option<&T> {
+        self.tiles.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.tiles.get_mut(&pos)
+    }
+
+    /// Whether there's a tile at `pos`
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.tiles.contains_key(&pos)
+    }
+
+    /// Set the tile at `pos`, growing the bounding box to fit it. Returns the tile it replaced.
+    pub fn set_point(&mut self, pos: Pos, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Pos::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Pos::new(max.x.max(pos.x), max.y.max(pos.y)),
+            ),
+            None => (pos, pos),
+        });
+        self.tiles.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        self.tiles.remove(&pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Top, right, bottom and left neighbours, skipping the ones that aren't set
+    pub fn cardinal_neighbours(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours_by(pos, &Pos::CARDINALS)
+    }
+
+    /// All 8 neighbours clockwise from the top, skipping the ones that aren't set
+    pub fn all_neighbours(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.neighbours_by(pos, &Pos::NEIGHBOURS)
+    }
+
+    fn neighbours_by<'a>(&'a self, pos: Pos, deltas: &'static [Pos]) -> impl Iterator<Item = (Pos, &'a T)> + 'a {
+        deltas.iter().filter_map(move |&delta| {
+            let next = pos + delta;
+            self.at_point(next).map(|tile| (next, tile))
+        })
+    }
+
+    /// The tiles that are set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.tiles.iter().map(|(&pos, tile)| (pos, tile))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        self.tiles.iter_mut().map(|(&pos, tile)| (pos, tile))
+    }
+
+    /// A dense copy of the bounding box with `background` where no tile is set. The top left
+    /// corner of the bounding box ends up at `Pos::ZERO`.
+    pub fn to_map(&self, background: T) -> Map<T>
+    where
+        T: Clone,
+    {
+        let Some((min, _)) = self.bounds else {
+            return Map::default();
+        };
+
+        let mut map = Map::new(self.width(), self.height(), background);
+        for (pos, tile) in self.iter() {
+            map[pos - min] = tile.clone();
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_grow() {
+        let mut map = SparseMap::new();
+        assert_eq!(map.bounds(), None);
+
+        map.set_point(Pos::new(2, -3), 'a');
+        map.set_point(Pos::new(-1, 4), 'b');
+        assert_eq!(map.set_point(Pos::new(2, -3), 'c'), Some('a'));
+
+        assert_eq!(map.bounds(), Some((Pos::new(-1, -3), Pos::new(2, 4))));
+        assert_eq!((map.width(), map.height()), (8, 4));
+        assert!(map.in_bounds(Pos::new(0, 0)));
+        assert!(!map.contains(Pos::new(0, 0)));
+        assert_eq!(map[Pos::new(2, -3)], 'c');
+        assert_eq!(map.len(), 2);
+
+        // Removing doesn't shrink the box
+        map.remove(Pos::new(-1, 4));
+        assert_eq!(map.bounds(), Some((Pos::new(-1, -3), Pos::new(2, 4))));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let map: SparseMap<char> = SparseMap::try_parse("#..\n.#.\n..#", '.').unwrap();
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.cardinal_neighbours(Pos::new(1, 1)).count(), 0);
+        let diagonal: Vec<(Pos, &char)> = map.all_neighbours(Pos::new(1, 1)).collect();
+        assert_eq!(diagonal, vec![(Pos::new(2, 2), &'#'), (Pos::new(0, 0), &'#')]);
+    }
+
+    #[test]
+    fn test_display_and_conversions() {
+        let map: SparseMap<char> = [(Pos::new(-1, -1), 'a'), (Pos::new(0, 1), 'b')].into_iter().collect();
+        assert_eq!(map.to_string(), "a..\n..b\n");
+
+        let dense = map.to_map(' ');
+        assert_eq!(dense.to_string(), "a  \n  b\n");
+
+        let back = SparseMap::from(&dense);
+        assert_eq!(back.len(), 6);
+        assert_eq!(back.at_point(Pos::new(1, 2)), Some(&'b'));
+        assert_eq!(SparseMap::<char>::new().to_map('.').to_string(), "");
+    }
+}
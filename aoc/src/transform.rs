@@ -0,0 +1,204 @@
+use crate::{Map, Pos};
+
+impl<T: Clone> Map<T> {
+    // A `height` by `width` map where each tile is copied from the one `source` points at
+    fn rebuild(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> (usize, usize)) -> Map<T> {
+        let rows = (0..height)
+            .map(|x| {
+                (0..width)
+                    .map(|y| {
+                        let (from_x, from_y) = source(x, y);
+                        self.row(from_x)[from_y].clone()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Map::from_rows(rows).with_topology(self.topology)
+    }
+
+    /// Turn the map clockwise by `quarter_turns` of 90 degrees, negative turns go anticlockwise
+    pub fn rotate(&self, quarter_turns: isize) -> Map<T> {
+        let (width, height) = (self.width, self.height);
+        match quarter_turns.rem_euclid(4) {
+            1 => self.rebuild(height, width, |x, y| (height - 1 - y, x)),
+            2 => self.rebuild(width, height, |x, y| (height - 1 - x, width - 1 - y)),
+            3 => self.rebuild(height, width, |x, y| (y, width - 1 - x)),
+            _ => self.clone(),
+        }
+    }
+
+    /// Rows become columns, mirroring the map in the diagonal from the top left
+    pub fn transpose(&self) -> Map<T> {
+        self.rebuild(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Mirror left and right
+    pub fn flip_horizontal(&self) -> Map<T> {
+        let width = self.width;
+        self.rebuild(width, self.height, |x, y| (x, width - 1 - y))
+    }
+
+    /// Mirror top and bottom
+    pub fn flip_vertical(&self) -> Map<T> {
+        let height = self.height;
+        self.rebuild(self.width, height, |x, y| (height - 1 - x, y))
+    }
+
+    /// The tiles from `min` to `max`, both inclusive. The parts of that box outside the map are
+    /// left out, so the result can be smaller or even empty.
+    pub fn crop(&self, min: Pos, max: Pos) -> Map<T> {
+        let top = min.x.clamp(0, self.height as isize) as usize;
+        let left = min.y.clamp(0, self.width as isize) as usize;
+        let bottom = (max.x + 1).clamp(top as isize, self.height as isize) as usize;
+        let right = (max.y + 1).clamp(left as isize, self.width as isize) as usize;
+
+        self.rebuild(right - left, bottom - top, |x, y| (top + x, left + y))
+    }
+
+    /// A border of `size` tiles of `tile` all around the map
+    pub fn pad(&self, size: usize, tile: T) -> Map<T> {
+        let mut padded = Map::new(self.width + 2 * size, self.height + 2 * size, tile).with_topology(self.topology);
+        let offset = Pos::from((size, size));
+        for (pos, tile) in self.iter() {
+            padded[pos + offset] = tile.clone();
+        }
+        padded
+    }
+}
+
+impl<T> Map<T> {
+    /// Replace every tile by a `height` by `width` block of tiles, given row after row by `block`.
+    /// Panics when a block has the wrong number of tiles.
+    pub fn scale<U>(&self, height: usize, width: usize, block: impl Fn(&T) -> Vec<U>) -> Map<U> {
+        let mut rows = vec![];
+        for row in self.rows() {
+            let mut blocks: Vec<_> = row
+                .iter()
+                .map(|tile| {
+                    let tiles = block(tile);
+                    let expected = height * width;
+                    assert_eq!(tiles.len(), expected, "A {}x{} block needs {} tiles", height, width, expected);
+                    tiles.into_iter()
+                })
+                .collect();
+
+            for _ in 0..height {
+                rows.push(blocks.iter_mut().flat_map(|tiles| tiles.take(width)).collect());
+            }
+        }
+
+        Map::from_rows(rows).with_topology(self.topology)
+    }
+
+    /// Every column, from left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|y| self.column(y))
+    }
+
+    /// Every straight line across the map in steps of `delta`, from edge to edge. The lines start
+    /// in reading order, so `lines(Pos::RIGHT)` are the rows and `lines(Pos::DOWN)` the columns.
+    /// Lines don't wrap around, whatever the topology.
+    pub fn lines(&self, delta: Pos) -> impl Iterator<Item = Vec<&T>> {
+        assert_ne!(delta, Pos::ZERO, "Lines need a direction");
+        let on_map = |pos: Pos| pos.to_index().is_some_and(|(x, y)| x < self.height && y < self.width);
+
+        self.iter()
+            .filter(move |&(pos, _)| !on_map(pos - delta))
+            .map(move |(start, _)| {
+                let mut line = vec![];
+                let mut pos = start;
+                while on_map(pos) {
+                    line.push(&self[pos]);
+                    pos += delta;
+                }
+                line
+            })
+    }
+
+    /// Lines going down and to the right
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        self.lines(Pos::DOWN + Pos::RIGHT)
+    }
+
+    /// Lines going down and to the left
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        self.lines(Pos::DOWN + Pos::LEFT)
+    }
+
+    /// Every `size` by `size` part of the map with the position of its top left corner, in reading
+    /// order. Neighbouring windows overlap.
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = (Pos, Map<&T>)> {
+        assert!(size > 0, "Windows need to be at least 1 tile");
+        let fits = move |length: usize| (length + 1).saturating_sub(size);
+
+        (0..fits(self.height)).flat_map(move |x| {
+            (0..fits(self.width)).map(move |y| {
+                let rows = (x..x + size).map(|row| self.row(row)[y..y + size].iter().collect()).collect();
+                (Pos::from((x, y)), Map::from_rows(rows))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Topology;
+
+    #[test]
+    fn test_rotate_and_flip() {
+        let map: Map<char> = Map::from("abc\ndef");
+
+        assert_eq!(map.rotate(1).to_string(), "da\neb\nfc\n");
+        assert_eq!(map.rotate(2).to_string(), "fed\ncba\n");
+        assert_eq!(map.rotate(-1).to_string(), "cf\nbe\nad\n");
+        assert_eq!(map.rotate(4).to_string(), map.to_string());
+        assert_eq!(map.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(map.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(map.flip_vertical().to_string(), "def\nabc\n");
+    }
+
+    #[test]
+    fn test_crop_pad_and_scale() {
+        let map: Map<char> = Map::from("abc\ndef").with_topology(Topology::Toroidal);
+
+        assert_eq!(map.crop(Pos::new(0, 1), Pos::new(5, 5)).to_string(), "bc\nef\n");
+        assert_eq!(map.crop(Pos::new(-3, -3), Pos::new(-1, -1)).to_string(), "");
+
+        let padded = map.pad(1, '.');
+        assert_eq!(padded.to_string(), ".....\n.abc.\n.def.\n.....\n");
+        assert_eq!(padded.topology, Topology::Toroidal);
+
+        let wide = map.scale(1, 2, |&c| vec![c, c.to_ascii_uppercase()]);
+        assert_eq!(wide.to_string(), "aAbBcC\ndDeEfF\n");
+        let big = map.crop(Pos::ZERO, Pos::ZERO).scale(2, 2, |&c| vec![c; 4]);
+        assert_eq!(big.to_string(), "aa\naa\n");
+    }
+
+    #[test]
+    fn test_lines() {
+        let map: Map<char> = Map::from("abc\ndef");
+        let strings = |lines: Vec<Vec<&char>>| -> Vec<String> {
+            lines.into_iter().map(|line| line.into_iter().collect()).collect()
+        };
+
+        assert_eq!(strings(map.lines(Pos::RIGHT).collect()), ["abc", "def"]);
+        assert_eq!(strings(map.lines(Pos::LEFT).collect()), ["cba", "fed"]);
+        assert_eq!(strings(map.diagonals().collect()), ["ae", "bf", "c", "d"]);
+        assert_eq!(strings(map.anti_diagonals().collect()), ["a", "bd", "ce", "f"]);
+        assert_eq!(map.columns().map(|column| column.collect::<String>()).collect::<Vec<_>>(), ["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn test_windows() {
+        let map: Map<char> = Map::from("abc\ndef\nghi");
+        let windows: Vec<(Pos, Map<&char>)> = map.windows(2).collect();
+
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[3].0, Pos::new(1, 1));
+        assert_eq!(windows[3].1.to_string(), "ef\nhi\n");
+        assert_eq!(map.windows(3).count(), 1);
+        assert_eq!(map.windows(4).count(), 0);
+    }
+}
@@ -0,0 +1,363 @@
+use std::fmt::Write as _;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use crate::render::Rgb;
+use crate::{Map, Pos};
+
+/// One character on the terminal, with optional 24-bit colours
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+}
+
+impl From<char> for Cell {
+    fn from(glyph: char) -> Self {
+        Cell { glyph, fg: None, bg: None }
+    }
+}
+
+impl Cell {
+    pub fn fg(self, colour: Rgb) -> Self {
+        Cell { fg: Some(colour), ..self }
+    }
+
+    pub fn bg(self, colour: Rgb) -> Self {
+        Cell { bg: Some(colour), ..self }
+    }
+}
+
+/// A highlight layer: whatever it sets replaces the cell's own glyph or colours
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub glyph: Option<char>,
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+}
+
+impl Style {
+    pub const VISITED: Style = Style {
+        glyph: None,
+        fg: None,
+        bg: Some(Rgb(60, 60, 60)),
+    };
+    pub const PATH: Style = Style {
+        glyph: None,
+        fg: Some(Rgb::WHITE),
+        bg: Some(Rgb::BLUE),
+    };
+    /// The guard, robot or reindeer
+    pub const ACTOR: Style = Style {
+        glyph: None,
+        fg: Some(Rgb::BLACK),
+        bg: Some(Rgb::YELLOW),
+    };
+
+    pub fn glyph(self, glyph: char) -> Self {
+        Style { glyph: Some(glyph), ..self }
+    }
+
+    fn apply(&self, cell: Cell) -> Cell {
+        Cell {
+            glyph: self.glyph.unwrap_or(cell.glyph),
+            fg: self.fg.or(cell.fg),
+            bg: self.bg.or(cell.bg),
+        }
+    }
+}
+
+/// What a simulation shows after a step: the map and a status line
+#[derive(Debug, Clone)]
+pub struct Frame {
+    cells: Map<Cell>,
+    pub status: String,
+}
+
+impl Frame {
+    pub fn from_map<T>(map: &Map<T>, mut cell: impl FnMut(&T) -> Cell) -> Self {
+        let rows = map.rows().map(|row| row.iter().map(&mut cell).collect()).collect();
+
+        Frame {
+            cells: Map::from_rows(rows),
+            status: String::new(),
+        }
+    }
+
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = status.into();
+        self
+    }
+
+    /// Layers are drawn in the order they're added, so add the actor last
+    pub fn highlight(&mut self, points: impl IntoIterator<Item = Pos>, style: Style) -> &mut Self {
+        for point in points {
+            if let Some(cell) = self.cells.get_mut(point) {
+                *cell = style.apply(*cell);
+            }
+        }
+        self
+    }
+
+    pub fn cell(&self, pos: Pos) -> Option<&Cell> {
+        self.cells.at_point(pos)
+    }
+}
+
+/// Typed on stdin followed by Enter while a simulation is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    /// `p`
+    TogglePause,
+    /// Enter on its own: show the next frame and pause
+    Step,
+    /// `+`
+    Faster,
+    /// `-`
+    Slower,
+    /// `q`: stop showing frames and let the simulation finish
+    Quit,
+}
+
+impl TryFrom<&str> for Control {
+    type Error = String;
+
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        match line.trim() {
+            "p" => Ok(Control::TogglePause),
+            "" => Ok(Control::Step),
+            "+" => Ok(Control::Faster),
+            "-" => Ok(Control::Slower),
+            "q" => Ok(Control::Quit),
+            other => Err(format!("Unknown control: {}", other)),
+        }
+    }
+}
+
+const HELP: &str = "p+Enter pause, Enter step, +/- speed, q quit";
+
+/// Draws frames on an ANSI terminal, only rewriting the cells that changed since the last one
+pub struct Viewer<W: Write> {
+    out: W,
+    previous: Option<Frame>,
+    delay: Duration,
+    paused: bool,
+    quit: bool,
+    controls: Option<Receiver<Control>>,
+    frames: usize,
+}
+
+impl Viewer<io::Stdout> {
+    /// Draw on stdout, reading controls from stdin when it's a terminal
+    pub fn stdout(delay: Duration) -> Self {
+        let mut viewer = Viewer::new(io::stdout(), delay);
+
+        if io::stdin().is_terminal() {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                for line in io::stdin().lock().lines().map_while(Result::ok) {
+                    if let Ok(control) = Control::try_from(line.as_str()) {
+                        if sender.send(control).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+            viewer.controls = Some(receiver);
+        }
+
+        viewer
+    }
+}
+
+impl<W: Write> Viewer<W> {
+    pub fn new(out: W, delay: Duration) -> Self {
+        Viewer {
+            out,
+            previous: None,
+            delay,
+            paused: false,
+            quit: false,
+            controls: None,
+            frames: 0,
+        }
+    }
+
+    /// Start paused, waiting for Enter before every frame
+    pub fn paused(mut self) -> Self {
+        self.paused = true;
+        self
+    }
+
+    /// Draw `frame`, then wait for the delay or the next control
+    pub fn show(&mut self, frame: &Frame) -> io::Result<()> {
+        if self.quit {
+            return Ok(());
+        }
+
+        self.frames += 1;
+        let output = self.draw(frame);
+        self.out.write_all(output.as_bytes())?;
+        self.out.flush()?;
+        self.previous = Some(frame.clone());
+
+        self.wait();
+        Ok(())
+    }
+
+    /// Move the cursor below the last frame, so the shell prompt doesn't overwrite it
+    pub fn finish(&mut self) -> io::Result<()> {
+        if let Some(previous) = &self.previous {
+            writeln!(self.out, "\x1b[{};1H", previous.cells.height + 2)?;
+        }
+        self.out.flush()
+    }
+
+    pub fn handle(&mut self, control: Control) {
+        match control {
+            Control::TogglePause => self.paused = !self.paused,
+            Control::Step => self.paused = true,
+            Control::Faster => self.delay /= 2,
+            Control::Slower => self.delay = (self.delay * 2).max(Duration::from_millis(1)),
+            Control::Quit => self.quit = true,
+        }
+    }
+
+    fn wait(&mut self) {
+        let Some(controls) = self.controls.take() else {
+            thread::sleep(self.delay);
+            return;
+        };
+
+        let mut waiting = true;
+        while waiting && !self.quit {
+            let control = if self.paused {
+                controls.recv().ok()
+            } else {
+                controls.recv_timeout(self.delay).ok()
+            };
+
+            match control {
+                // Stepping shows exactly one more frame
+                Some(Control::Step) => {
+                    self.handle(Control::Step);
+                    waiting = false;
+                }
+                Some(control) => self.handle(control),
+                None => waiting = false,
+            }
+        }
+
+        self.controls = Some(controls);
+    }
+
+    fn draw(&self, frame: &Frame) -> String {
+        let mut output = String::new();
+        let same_size = self
+            .previous
+            .as_ref()
+            .is_some_and(|previous| (previous.cells.width, previous.cells.height) == (frame.cells.width, frame.cells.height));
+
+        if !same_size {
+            output.push_str("\x1b[2J\x1b[H");
+        }
+
+        for (pos, cell) in frame.cells.iter() {
+            let changed = !same_size || self.previous.as_ref().and_then(|previous| previous.cell(pos)) != Some(cell);
+            if changed {
+                // Terminal rows and columns count from 1
+                write!(output, "\x1b[{};{}H", pos.x + 1, pos.y + 1).unwrap();
+                push_cell(&mut output, cell);
+            }
+        }
+
+        let pause = if self.paused { " (paused)" } else { "" };
+        write!(
+            output,
+            "\x1b[{};1H\x1b[2KFrame {}{} | {}ms | {}\n\x1b[2K{}",
+            frame.cells.height + 1,
+            self.frames,
+            pause,
+            self.delay.as_millis(),
+            HELP,
+            frame.status
+        )
+        .unwrap();
+
+        output
+    }
+}
+
+fn push_cell(output: &mut String, cell: &Cell) {
+    if let Some(Rgb(r, g, b)) = cell.fg {
+        write!(output, "\x1b[38;2;{};{};{}m", r, g, b).unwrap();
+    }
+    if let Some(Rgb(r, g, b)) = cell.bg {
+        write!(output, "\x1b[48;2;{};{};{}m", r, g, b).unwrap();
+    }
+    output.push(cell.glyph);
+    if cell.fg.is_some() || cell.bg.is_some() {
+        output.push_str("\x1b[0m");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(map: &str) -> Frame {
+        Frame::from_map(&Map::<char>::from(map), |&c| Cell::from(c))
+    }
+
+    #[test]
+    fn test_highlight_layers() {
+        let mut frame = frame("...\n.#.");
+        frame
+            .highlight([Pos::new(0, 0), Pos::new(0, 1)], Style::VISITED)
+            .highlight([Pos::new(0, 1), Pos::new(5, 5)], Style::ACTOR.glyph('^'));
+
+        assert_eq!(frame.cell(Pos::new(0, 0)), Some(&Cell::from('.').bg(Rgb(60, 60, 60))));
+        assert_eq!(frame.cell(Pos::new(0, 1)), Some(&Cell::from('^').fg(Rgb::BLACK).bg(Rgb::YELLOW)));
+        assert_eq!(frame.cell(Pos::new(1, 1)), Some(&Cell::from('#')));
+    }
+
+    #[test]
+    fn test_redraws_changed_cells_only() {
+        let mut viewer = Viewer::new(vec![], Duration::ZERO);
+
+        viewer.show(&frame("...\n.#.")).unwrap();
+        let first = String::from_utf8(std::mem::take(&mut viewer.out)).unwrap();
+        assert!(first.starts_with("\x1b[2J"));
+        assert_eq!(first.matches('.').count(), 5);
+
+        viewer.show(&frame("...\n.#@").status("moved")).unwrap();
+        let second = String::from_utf8(std::mem::take(&mut viewer.out)).unwrap();
+        assert!(!second.contains("\x1b[2J"));
+        assert!(second.starts_with("\x1b[2;3H@"));
+        assert!(second.ends_with("moved"));
+        assert!(!second.contains('#'));
+    }
+
+    #[test]
+    fn test_controls() {
+        let mut viewer = Viewer::new(vec![], Duration::from_millis(100));
+
+        viewer.handle(Control::try_from("+").unwrap());
+        assert_eq!(viewer.delay, Duration::from_millis(50));
+        viewer.handle(Control::try_from("-\n").unwrap());
+        viewer.handle(Control::Slower);
+        assert_eq!(viewer.delay, Duration::from_millis(200));
+        viewer.handle(Control::try_from("").unwrap());
+        assert!(viewer.paused);
+        viewer.handle(Control::try_from("p").unwrap());
+        assert!(!viewer.paused);
+        assert!(Control::try_from("x").is_err());
+
+        viewer.handle(Control::Quit);
+        viewer.show(&frame(".")).unwrap();
+        assert!(viewer.out.is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use aoc::Solution;
+
+pub struct Lists {
+    list1: Vec<i32>,
+    list2: Vec<i32>,
+}
+
+impl From<&str> for Lists {
+    fn from(s: &str) -> Self {
+        let (list1, list2) = s
+            .lines()
+            .map(|line| {
+                line.split_once("   ")
+                    .map(|(a, b)| (a.parse::<i32>().unwrap(), b.parse::<i32>().unwrap()))
+                    .unwrap()
+            })
+            .unzip();
+
+        Lists { list1, list2 }
+    }
+}
+
+impl Lists {
+    fn sorted(&self) -> (Vec<i32>, Vec<i32>) {
+        let mut list1 = self.list1.clone();
+        let mut list2 = self.list2.clone();
+        list1.sort_unstable();
+        list2.sort_unstable();
+
+        (list1, list2)
+    }
+
+    fn distance(&self) -> i32 {
+        let (list1, list2) = self.sorted();
+
+        list1.iter().enumerate().fold(0, |acc, (i, x)| {
+            acc + x.abs_diff(list2[i]) as i32
+        })
+    }
+
+    fn similarity_score(&self) -> i32 {
+        self.list1.iter().fold(0, |acc, x| {
+            acc + x * self.list2.iter().filter(|y| x == *y ).count() as i32
+        })
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+
+    type Input = Lists;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Lists {
+        Lists::from(input)
+    }
+
+    fn part1(lists: &Lists) -> i32 {
+        lists.distance()
+    }
+
+    fn part2(lists: &Lists) -> i32 {
+        lists.similarity_score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Example data from AoC
+const EXAMPLE: &str =  "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(11, Day01::part1(&Day01::parse(EXAMPLE)));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(31, Day01::part2(&Day01::parse(EXAMPLE)));
+    }
+}
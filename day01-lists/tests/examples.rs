@@ -0,0 +1 @@
+aoc::example_tests!(day01_lists::Day01);
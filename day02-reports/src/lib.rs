@@ -0,0 +1,113 @@
+use aoc::Solution;
+
+pub struct Report {
+    levels: Vec<i32>,
+}
+
+impl From<&str> for Report {
+    fn from(input: &str) -> Self {
+        let levels = input
+            .split_whitespace()
+            .filter_map(|s| s.parse().ok())
+            .collect();
+
+        Self { levels }
+    }
+}
+
+impl Report {
+    fn str_is_safe(levels: &[i32]) -> bool {
+        let differences: Vec<i32> = levels
+        .windows(2)
+        .map(|window| window[1] - window[0])
+        .collect();
+
+        differences.iter().all(|&x| x > 0 && x < 4) ||
+            differences.iter().all(|&x| x < 0 && x > -4)
+    }
+
+    fn is_safe(&self) -> bool {
+        Report::str_is_safe(&self.levels)
+    }
+
+    fn is_dampened_safe(&self) -> bool {
+        if Report::str_is_safe(&self.levels) {
+            return true;
+        }
+
+        self.levels.iter().enumerate().any(|(i, _)| {
+            let mut levels = self.levels.clone();
+            levels.remove(i);
+
+            Report::str_is_safe(&levels)
+        })
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    type Input = Vec<Report>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Vec<Report> {
+        input.lines().map(Report::from).collect()
+    }
+
+    fn part1(reports: &Vec<Report>) -> usize {
+        reports.iter()
+               .filter(|report| report.is_safe())
+               .count()
+    }
+
+    fn part2(reports: &Vec<Report>) -> usize {
+        reports.iter()
+               .filter(|report| report.is_dampened_safe())
+               .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+    #[test]
+    fn test_input() {
+        assert_eq!(Day02::part1(&Day02::parse(EXAMPLE)), 2);
+    }
+
+    #[test]
+    fn test_step2() {
+        assert_eq!(Day02::part2(&Day02::parse(EXAMPLE)), 4);
+    }
+
+
+    #[test]
+    fn test_is_safe() {
+        assert!(Report::from("1 2 3 4 5").is_safe());
+        assert!(Report::from("7 6 4 2 1").is_safe());
+        assert!(!Report::from("1 2 7 8 9").is_safe());
+        assert!(!Report::from("1 3 2 4 5").is_safe());
+    }
+
+    #[test]
+    fn test_is_dampened_safe() {
+        assert!(Report::from("7 6 4 2 1").is_dampened_safe());
+        assert!(Report::from("1 3 2 4 5").is_dampened_safe());
+        assert!(Report::from("8 6 4 4 1").is_dampened_safe());
+        assert!(!Report::from("1 2 7 8 9").is_dampened_safe());
+        assert!(!Report::from("9 7 6 2 1").is_dampened_safe());
+    }
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day02_reports::Day02);
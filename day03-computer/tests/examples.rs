@@ -0,0 +1 @@
+aoc::example_tests!(day03_computer::Day03);
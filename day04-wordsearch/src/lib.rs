@@ -0,0 +1,89 @@
+use aoc::{Map, Pos, Solution};
+
+pub struct Puzzle {
+    grid: Map<char>,
+}
+
+impl From<&str> for Puzzle {
+    fn from(input: &str) -> Self {
+        Self { grid: Map::from(input) }
+    }
+}
+
+impl Puzzle {
+    // Forwards and backwards along every row, column and diagonal
+    fn word_count(&self, word: &str) -> u32 {
+        let backwards: String = word.chars().rev().collect();
+
+        [Pos::RIGHT, Pos::DOWN, Pos::DOWN + Pos::RIGHT, Pos::DOWN + Pos::LEFT]
+            .into_iter()
+            .flat_map(|delta| self.grid.lines(delta))
+            .map(|line| {
+                let line: String = line.into_iter().collect();
+                line.matches(word).count() + line.matches(&backwards).count()
+            })
+            .sum::<usize>() as u32
+    }
+
+    // Just hardcode we're looking for the MAS cross, either diagonal can be read both ways
+    fn cross_count(&self) -> u32 {
+        let is_mas = |word: [char; 3]| word == ['M', 'A', 'S'] || word == ['S', 'A', 'M'];
+
+        self.grid.windows(3)
+            .filter(|(_, window)| {
+                let at = |x, y| *window[Pos::new(x, y)];
+                is_mas([at(0, 0), at(1, 1), at(2, 2)]) && is_mas([at(0, 2), at(1, 1), at(2, 0)])
+            })
+            .count() as u32
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+
+    type Input = Puzzle;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Puzzle {
+        Puzzle::from(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> u32 {
+        puzzle.word_count("XMAS")
+    }
+
+    fn part2(puzzle: &Puzzle) -> u32 {
+        puzzle.cross_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn step1_example() {
+        assert_eq!(Day04::part1(&Day04::parse(EXAMPLE)), 18);
+    }
+
+    #[test]
+    fn step2_example() {
+        assert_eq!(Day04::part2(&Day04::parse(EXAMPLE)), 9);
+    }
+}
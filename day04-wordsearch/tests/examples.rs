@@ -0,0 +1 @@
+aoc::example_tests!(day04_wordsearch::Day04);
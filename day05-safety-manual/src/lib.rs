@@ -0,0 +1,131 @@
+use aoc::parse::{self, List, Pair, ParseError};
+use aoc::Solution;
+use std::collections::HashMap;
+
+type OrderingRule = (usize, usize);
+type Update = Vec<usize>;
+
+pub struct Puzzle {
+    rules: Vec<OrderingRule>,
+    updates: Vec<Update>,
+}
+
+impl From<&str> for Puzzle {
+    fn from(input: &str) -> Self {
+        Self::try_parse(input).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl Puzzle {
+    fn try_parse(input: &str) -> Result<Self, ParseError> {
+        let [rule_input, update_input] = parse::exact_sections(input)?;
+        let rules = rule_input
+            .records::<Pair<usize, '|'>>()?
+            .into_iter()
+            .map(|Pair(l, r)| (l, r))
+            .collect();
+
+        let updates = update_input
+            .records::<List<usize, ','>>()?
+            .into_iter()
+            .map(|List(update)| update)
+            .collect();
+
+        Ok(Self { rules, updates })
+    }
+
+    fn is_valid(&self, update: &Update) -> bool {
+        let update_set: HashMap<&usize, usize> = update
+            .iter()
+            .enumerate()
+            .map(|(idx, val)| (val, idx))
+            .collect();
+        let relevant_rules: Vec<&OrderingRule> = self
+            .rules
+            .iter()
+            .filter(|(l, r)| update_set.contains_key(&l) && update_set.contains_key(&r))
+            .collect();
+
+        relevant_rules.iter().all(|(l, r)| {
+            let l_idx = update_set[l];
+            let r_idx = update_set[r];
+            l_idx < r_idx
+        })
+    }
+
+    fn checked_updates(&self, valid: bool) -> Vec<&Update> {
+        self.updates
+            .iter()
+            .filter(|update| self.is_valid(update) == valid)
+            .collect()
+    }
+
+    fn correct_update(&self, update: &Update) -> Update {
+        let update_set: HashMap<&usize, usize> = update
+            .iter()
+            .enumerate()
+            .map(|(idx, val)| (val, idx))
+            .collect();
+        let relevant_rules: Vec<&OrderingRule> = self
+            .rules
+            .iter()
+            .filter(|(l, r)| update_set.contains_key(&l) && update_set.contains_key(&r))
+            .collect();
+
+        let mut corrected_update = update.clone();
+        corrected_update.sort_by(|a, b| {
+            relevant_rules
+                .iter()
+                .find(|(l, r)| a == l && b == r)
+                .map(|_| std::cmp::Ordering::Less)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        corrected_update
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+
+    type Input = Puzzle;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Puzzle {
+        Puzzle::from(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> u32 {
+        puzzle
+            .checked_updates(true)
+            .iter()
+            .map(|update| update[update.len() / 2] as u32)
+            .sum::<u32>()
+    }
+
+    fn part2(puzzle: &Puzzle) -> u32 {
+        puzzle
+            .checked_updates(false)
+            .iter()
+            .map(|update| puzzle.correct_update(update))
+            .map(|update| update[update.len() / 2] as u32)
+            .sum::<u32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_from_str() {
+        let input = "0|1\n2|3\n\n1,2,3\n4,5,6";
+
+        let puzzle = Puzzle::from(input);
+        assert_eq!(puzzle.rules, vec![(0, 1), (2, 3)]);
+        assert_eq!(puzzle.updates, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+}
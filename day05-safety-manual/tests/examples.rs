@@ -0,0 +1 @@
+aoc::example_tests!(day05_safety_manual::Day05);
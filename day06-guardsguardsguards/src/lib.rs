@@ -0,0 +1,169 @@
+use aoc::viz::{Cell, Frame, Style};
+use aoc::cycle;
+use aoc::{Direction, Pos, Solution, Walker};
+use std::collections::HashSet;
+
+#[derive(Clone)]
+pub struct Map {
+    // Data holds a map where true is an obstruction and false is open space
+    data: aoc::Map<bool>,
+    position: Walker,
+}
+
+impl From<&str> for Map {
+    fn from(value: &str) -> Self {
+        let mut position = Walker::new(Pos::ZERO, Direction::North);
+        let rows = value
+            .lines()
+            .enumerate()
+            .map(|(x, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(y, c)| match c {
+                        '^' => {
+                            position = Walker::new(Pos::from((x, y)), Direction::North);
+                            false
+                        }
+                        '#' => true,
+                        '.' => false,
+                        _ => panic!("Invalid character in map"),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self { data: aoc::Map::from_rows(rows), position }
+    }
+}
+
+impl Map {
+    fn step(&mut self) -> bool {
+        match self.position.peek(&self.data) {
+            Some(true) => self.position.turn_right(),
+            Some(false) => self.position.step(),
+            None => return false,
+        }
+
+        true
+    }
+
+    // Jump straight to the next obstacle instead of walking there, `None` once the guard walks off
+    // the map instead
+    fn next_turn(&self, mut guard: Walker) -> Option<Walker> {
+        let ahead = guard.heading.delta();
+        let (obstacle, _) = self.data.first_hit(guard.pos, ahead, |&blocked| blocked)?;
+
+        guard.pos = obstacle - ahead;
+        guard.turn_right();
+        Some(guard)
+    }
+
+    // Turning at the same place in the same direction again is a loop
+    fn is_loop(&self) -> bool {
+        cycle::brent(self.position, |&guard| self.next_turn(guard)).is_some()
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+
+    type Input = Map;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Map {
+        Map::from(input)
+    }
+
+    fn part1(map: &Map) -> i32 {
+        part1(map.clone())
+    }
+
+    fn part2(map: &Map) -> i32 {
+        part2(map.clone())
+    }
+
+    // The guard's walk from part 1
+    fn watch(map: &Map, show: &mut dyn FnMut(&Frame)) -> Result<(), String> {
+        let mut map = map.clone();
+        let mut visited_positions: HashSet<Pos> = HashSet::from([map.position.pos]);
+
+        loop {
+            let guard = map.position;
+            let mut frame = Frame::from_map(&map.data, |&blocked| Cell::from(if blocked { '#' } else { '.' }))
+                .status(format!("{} positions visited", visited_positions.len()));
+            frame
+                .highlight(visited_positions.iter().copied(), Style::VISITED)
+                .highlight([guard.pos], Style::ACTOR.glyph(guard.heading.arrow().unwrap()));
+            show(&frame);
+
+            if !map.step() {
+                return Ok(());
+            }
+            visited_positions.insert(map.position.pos);
+        }
+    }
+}
+
+fn part1(mut map: Map) -> i32 {
+    let mut visited_positions: HashSet<Pos> = HashSet::new();
+
+    visited_positions.insert(map.position.pos);
+    while map.step() {
+        visited_positions.insert(map.position.pos);
+    }
+
+    visited_positions.len() as i32
+}
+
+fn part2(mut map: Map) -> i32 {
+    let mut visited_positions: HashSet<Pos> = HashSet::new();
+    let start = map.position;
+
+    while map.step() {
+        visited_positions.insert(map.position.pos);
+    }
+
+    // Let's see if we get a loop by putting something in the way on every visited position,
+    // reusing one copy of the map so every candidate doesn't allocate a new one
+    let mut candidate = map.clone();
+    visited_positions.into_iter()
+        .filter(|&pos| {
+            candidate.data.clone_from(&map.data);
+            candidate.data[pos] = true;
+            candidate.position = start;
+            candidate.is_loop()
+        })
+        .count() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...\
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day06::part1(&Day06::parse(EXAMPLE)), 41);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day06::part2(&Day06::parse(EXAMPLE)), 6);
+    }
+}
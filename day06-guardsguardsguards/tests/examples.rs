@@ -0,0 +1 @@
+aoc::example_tests!(day06_guardsguardsguards::Day06);
@@ -0,0 +1,115 @@
+use aoc::Solution;
+use rayon::prelude::*;
+
+pub struct Calibration {
+    result: u64,
+    numbers: Vec<u64>,
+}
+
+impl From<&str> for Calibration {
+    fn from(input: &str) -> Self {
+        let (result, numbers) = input
+            .split_once(": ")
+            .map(|(result, numbers)| {
+                (
+                    result.parse().unwrap(),
+                    numbers.split(" ").map(|n| n.parse().unwrap()).collect(),
+                )
+            })
+            .expect("Error parsing calibration input");
+
+        Self { result, numbers }
+    }
+}
+
+fn mul(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(b)
+}
+
+fn add(a: u64, b: u64) -> Option<u64> {
+    a.checked_add(b)
+}
+
+fn combine(a: u64, b: u64) -> Option<u64> {
+    Some(a * 10u64.pow((b as f64).log10().ceil() as u32) + b)
+}
+
+type Operation = fn(u64, u64) -> Option<u64>;
+
+const OPERATIONS: [Operation; 2] = [mul, add];
+const ALL_OPERATIONS: [Operation; 3] = [mul, add, combine];
+
+impl Calibration {
+    fn try_all_operations(total: u64, max: &u64, numbers: &[u64], operations: &[Operation]) -> Vec<u64> {
+        if *max < total {
+            return vec![];
+        }
+        if let Some((number, rest)) = numbers.split_first() {
+            let mut results = vec![];
+
+            for operation in operations {
+                if let Some(result) = operation(total, *number) {
+                    results.extend(Self::try_all_operations(result, max, rest, operations));
+                }
+            };
+
+            results
+        } else {
+            vec![total]
+        }
+    }
+
+    fn is_solvable(&self, operations: &[Operation]) -> bool {
+        Self::try_all_operations(self.numbers[0], &self.result, &self.numbers[1..], operations).contains(&self.result)
+    }
+}
+
+fn total(calibrations: &[Calibration], operations: &[Operation]) -> u64 {
+    calibrations
+        .par_iter()
+        .filter(|calibration| calibration.is_solvable(operations))
+        .map(|calibration| calibration.result)
+        .sum()
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+
+    type Input = Vec<Calibration>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Vec<Calibration> {
+        input.par_lines().map(Calibration::from).collect()
+    }
+
+    fn part1(calibrations: &Vec<Calibration>) -> u64 {
+        total(calibrations, &OPERATIONS)
+    }
+
+    fn part2(calibrations: &Vec<Calibration>) -> u64 {
+        total(calibrations, &ALL_OPERATIONS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_is_solvable() {
+        assert!(Calibration::from("190: 10 19").is_solvable(&ALL_OPERATIONS));
+        assert!(Calibration::from("3267: 81 40 27").is_solvable(&ALL_OPERATIONS));
+        assert!(!Calibration::from("161011: 16 10 13").is_solvable(&ALL_OPERATIONS));
+    }
+
+    #[test]
+    fn test_calibration_is_solvable_without_combine() {
+        assert!(Calibration::from("3267: 81 40 27").is_solvable(&OPERATIONS));
+        assert!(!Calibration::from("156: 15 6").is_solvable(&OPERATIONS));
+        assert!(Calibration::from("156: 15 6").is_solvable(&ALL_OPERATIONS));
+    }
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day07_calibration::Day07);
@@ -0,0 +1,83 @@
+use aoc::{Map, Pos, Solution};
+use std::collections::{HashMap, HashSet};
+
+pub struct City {
+    map: Map<char>,
+    antennas: HashMap<char, Vec<Pos>>,
+}
+
+impl From<&str> for City {
+    fn from(input: &str) -> Self {
+        let map: Map<char> = Map::from(input);
+        let mut antennas: HashMap<char, Vec<Pos>> = HashMap::new();
+        for (pos, &c) in map.iter().filter(|&(_, &c)| c != '.') {
+            antennas.entry(c).or_default().push(pos);
+        }
+
+        City { map, antennas }
+    }
+}
+
+impl City {
+    fn combinations(antennas: &[Pos]) -> impl Iterator<Item = (Pos, Pos)> + '_ {
+        antennas.iter().flat_map(move |&antenna| {
+            antennas
+                .iter()
+                .filter(move |&&other| other != antenna)
+                .map(move |&other| (antenna, other))
+        })
+    }
+
+    // Twice as far from the other antenna, on the far side of this one
+    fn antinodes_for(&self, antennas: &[Pos]) -> Vec<Pos> {
+        Self::combinations(antennas)
+            .map(|(antenna, other)| antenna + (antenna - other))
+            .filter(|&pos| self.map.contains(pos))
+            .collect()
+    }
+
+    // Every position in line with the two antennas. Stepping by the reduced delta also finds the
+    // positions between them, when there are any.
+    fn repeated_antinodes_for(&self, antennas: &[Pos]) -> Vec<Pos> {
+        Self::combinations(antennas)
+            .flat_map(|(antenna, other)| {
+                let step = (antenna - other).reduced();
+                let line = self.map.ray(antenna, step).chain(self.map.ray(antenna, -step));
+                std::iter::once(antenna).chain(line.map(|(pos, _)| pos))
+            })
+            .collect()
+    }
+
+    fn placeable_antinodes(&self, repeatable: bool) -> HashSet<Pos> {
+        self.antennas.values().flat_map(|antennas| {
+            if repeatable {
+                self.repeated_antinodes_for(antennas)
+            } else {
+                self.antinodes_for(antennas)
+            }
+        }).collect()
+    }
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+
+    type Input = City;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> City {
+        City::from(input)
+    }
+
+    fn part1(city: &City) -> usize {
+        city.placeable_antinodes(false).len()
+    }
+
+    fn part2(city: &City) -> usize {
+        city.placeable_antinodes(true).len()
+    }
+}
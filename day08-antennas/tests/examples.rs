@@ -0,0 +1 @@
+aoc::example_tests!(day08_antennas::Day08);
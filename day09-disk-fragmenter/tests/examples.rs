@@ -0,0 +1 @@
+aoc::example_tests!(day09_disk_fragmenter::Day09);
@@ -0,0 +1,148 @@
+use aoc::search::{bfs, Search};
+use aoc::Solution;
+
+type Point = (usize, usize);
+pub struct Trails {
+    grid: Vec<Vec<usize>>,
+    trail_starts: Vec<Point>,
+}
+
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+impl From<&str> for Trails {
+    fn from(input: &str) -> Self {
+        let mut trail_starts = vec![];
+        let grid = input.lines().enumerate().map(|(x, line)| {
+            line.chars().enumerate().map(|(y, c)| {
+                if c == '0' {
+                    trail_starts.push((x, y));
+                }
+                c.to_digit(10).unwrap() as usize
+            }).collect()
+        }).collect();
+
+        Self { grid, trail_starts }
+    }
+}
+
+impl Trails {
+    fn moved(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(&usize, Point)> {
+        let next_pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        let next = self.grid.get(next_pos.0)?.get(next_pos.1)?;
+
+        Some((next, next_pos))
+    }
+
+    // Every step goes up by exactly one, so every trail is one of the shortest paths to its end
+    fn trails_from(&self, trail_start: &Point) -> Search<Point, u32> {
+        bfs(
+            [*trail_start],
+            |&(x, y)| {
+                let current = self.grid[x][y];
+
+                DIRECTIONS
+                    .iter()
+                    .filter_map(|&(dx, dy)| self.moved(x, y, dx, dy))
+                    .filter(|&(&next, _)| next == current + 1)
+                    .map(|(_, next_pos)| next_pos)
+                    .collect::<Vec<Point>>()
+            },
+            |_| false,
+        )
+    }
+
+    fn reachable_ends(&self, trails: &Search<Point, u32>) -> Vec<Point> {
+        trails
+            .costs()
+            .filter(|&(&(x, y), _)| self.grid[x][y] == 9)
+            .map(|(&end, _)| end)
+            .collect()
+    }
+
+    fn trail_score(&self, trail_start: &Point) -> usize {
+        self.reachable_ends(&self.trails_from(trail_start)).len()
+    }
+
+    fn trail_rating(&self, trail_start: &Point) -> usize {
+        let trails = self.trails_from(trail_start);
+
+        self.reachable_ends(&trails).iter().map(|end| trails.count_paths(end) as usize).sum()
+    }
+
+    fn score(&self) -> usize {
+        self.trail_starts.iter().map(|start| self.trail_score(start)).sum()
+    }
+
+    fn rating(&self) -> usize {
+        self.trail_starts.iter().map(|start| self.trail_rating(start)).sum()
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Hoof It";
+
+    type Input = Trails;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Trails {
+        Trails::from(input)
+    }
+
+    fn part1(trails: &Trails) -> usize {
+        trails.score()
+    }
+
+    fn part2(trails: &Trails) -> usize {
+        trails.rating()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732\
+";
+    #[test]
+    fn test_trails_from() {
+        let trails = Trails::from(EXAMPLE);
+
+        assert_eq!(9, trails.trail_starts.len());
+        let trail_start = trails.trail_starts[0];
+        assert_eq!(0, trails.grid[trail_start.0][trail_start.1]);
+    }
+
+    #[test]
+    fn test_trail_score() {
+        let trails = Trails::from(EXAMPLE);
+
+        let trail_start = trails.trail_starts[0];
+        assert_eq!(5, trails.trail_score(&trail_start));
+    }
+
+    #[test]
+    fn test_score() {
+        let trails = Trails::from(EXAMPLE);
+
+        assert_eq!(36, trails.score());
+    }
+
+    #[test]
+    fn test_rating() {
+        let trails = Trails::from(EXAMPLE);
+
+        assert_eq!(81, trails.rating());
+    }
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day10_hoof_it::Day10);
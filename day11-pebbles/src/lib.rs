@@ -0,0 +1,104 @@
+use aoc::Solution;
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct Stones {
+    stones: Vec<u64>,
+    cache: HashMap<(u64, u32), u64>
+}
+
+impl From<&str> for Stones {
+    fn from(input: &str) -> Self {
+        let stones = input.split_whitespace().map(|s| s.parse().unwrap() ).collect();
+
+        Self { stones, cache: HashMap::new() }
+    }
+}
+
+impl Stones {
+    fn blink(&mut self, n: u32, queue: Option<Vec<u64>>) -> u64 {
+        let queue = queue.unwrap_or(self.stones.clone());
+
+        queue.iter().map(|&stone| {
+            if let Some(&count) = self.cache.get(&(stone, n)) {
+                return count;
+            }
+
+            self.blink_stone(stone).iter().map(|&new_stone| {
+                if n == 1 {
+                    return 1;
+                }
+
+                let count = self.blink(n - 1, Some(vec![new_stone]));
+                self.cache.insert((new_stone, n - 1), count);
+
+                count
+            }).sum()
+        }).sum()
+    }
+
+    fn blink_stone(&mut self, stone: u64) -> Vec<u64> {
+        if stone == 0 {
+            return vec![1];
+        }
+
+        let num_digits = (stone as f64).log10().floor() as u32 + 1;
+        if num_digits.is_multiple_of(2) {
+            let left_stone = stone / 10u64.pow(num_digits / 2);
+            let right_stone = stone % 10u64.pow(num_digits / 2);
+
+            return vec![left_stone, right_stone]
+        }
+
+        vec![stone * 2024]
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+
+    type Input = Stones;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Stones {
+        Stones::from(input)
+    }
+
+    fn part1(stones: &Stones) -> u64 {
+        stones.clone().blink(25, None)
+    }
+
+    fn part2(stones: &Stones) -> u64 {
+        stones.clone().blink(75, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "125 17";
+
+    #[test]
+    fn test_1_blink() {
+        let mut stones = Stones::from(EXAMPLE);
+        assert_eq!(stones.blink(1, None), 3);
+    }
+
+    #[test]
+    fn test_2_blink() {
+        let mut stones = Stones::from(EXAMPLE);
+        assert_eq!(stones.blink(2, None), 4);
+    }
+
+    #[test]
+    fn test_25_blink() {
+        let mut stones = Stones::from(EXAMPLE);
+
+        assert_eq!(stones.blink(25, None), 55312);
+    }
+}
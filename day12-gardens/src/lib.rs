@@ -0,0 +1,34 @@
+use aoc::{Connectivity, Map, Regions, Solution};
+
+fn price(plots: &Regions) -> usize {
+    plots.iter().map(|plot| plot.area() * plot.perimeter).sum()
+}
+
+// A straight fence costs the same however long it is
+fn discounted_price(plots: &Regions) -> usize {
+    plots.iter().map(|plot| plot.area() * plot.sides).sum()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Garden Groups";
+
+    type Input = Regions;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Regions {
+        let map: Map<char> = Map::from(input);
+        map.regions(Connectivity::Four, |a, b| a == b)
+    }
+
+    fn part1(plots: &Regions) -> usize {
+        price(plots)
+    }
+
+    fn part2(plots: &Regions) -> usize {
+        discounted_price(plots)
+    }
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day12_gardens::Day12);
@@ -0,0 +1,160 @@
+use aoc::gen::{self, Rng};
+use aoc::parse::{self, Integers};
+use aoc::{Part, Solution};
+
+const PART2_OFFSET: i64 = 10000000000000;
+
+// Button A: X+94, Y+34
+// Button B: X+22, Y+67
+// Prize: X=8400, Y=5400
+type Machine = [i64; 6];
+
+fn parse_machine(input: &str) -> Machine {
+    let Integers(machine) = input.parse().unwrap_or_else(|error| panic!("{}", error));
+    machine
+}
+
+// How often A and B are pressed to reach the prize, by Cramer's rule. Buttons that move in the same
+// direction never show up in the puzzle, so there's at most one way to win.
+fn presses(machine: &Machine, offset: i64) -> Option<(i64, i64)> {
+    let [x1, y1, x2, y2, r1, r2] = *machine;
+    let (r1, r2) = (r1 + offset, r2 + offset);
+
+    let determinant = x1 * y2 - y1 * x2;
+    let times_a = r1 * y2 - r2 * x2;
+    let times_b = x1 * r2 - y1 * r1;
+
+    if determinant == 0 || times_a % determinant != 0 || times_b % determinant != 0 {
+        return None;
+    }
+    let (times_a, times_b) = (times_a / determinant, times_b / determinant);
+
+    (times_a >= 0 && times_b >= 0).then_some((times_a, times_b))
+}
+
+fn solve_machine(machine: &Machine, offset: i64) -> Option<i64> {
+    presses(machine, offset).map(|(times_a, times_b)| times_a * 3 + times_b)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Claw Contraption";
+
+    type Input = Vec<Machine>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Vec<Machine> {
+        parse::sections(input)
+            .iter()
+            .map(|machine| machine.parse::<Integers<6>>().map(|Integers(machine)| machine))
+            .collect::<Result<_, _>>()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn part1(machines: &Vec<Machine>) -> i64 {
+        // No button is pressed more than 100 times in part 1
+        machines
+            .iter()
+            .filter_map(|machine| presses(machine, 0))
+            .filter(|&(times_a, times_b)| times_a <= 100 && times_b <= 100)
+            .map(|(times_a, times_b)| times_a * 3 + times_b)
+            .sum()
+    }
+
+    fn part2(machines: &Vec<Machine>) -> i64 {
+        machines.iter().filter_map(|machine| solve_machine(machine, PART2_OFFSET)).sum()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let machines: Vec<String> = (0..size)
+            .map(|_| {
+                let (mut a, mut b) = ((0, 0), (0, 0));
+                while a.0 * b.1 == a.1 * b.0 {
+                    a = (rng.range(1..100), rng.range(1..100));
+                    b = (rng.range(1..100), rng.range(1..100));
+                }
+
+                // Half the prizes can be won, some of those only with more than 100 presses
+                let prize = if rng.chance(0.5) {
+                    let (times_a, times_b) = (rng.range(0..120), rng.range(0..120));
+                    (times_a * a.0 + times_b * b.0, times_a * a.1 + times_b * b.1)
+                } else {
+                    (rng.range(0..20000), rng.range(0..20000))
+                };
+
+                format!(
+                    "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}",
+                    a.0, a.1, b.0, b.1, prize.0, prize.1
+                )
+            })
+            .collect();
+
+        Some(machines.join("\n\n"))
+    }
+
+    // Tries every number of presses, which only works for part 1
+    fn reference(input: &str, part: Part) -> Option<String> {
+        if part == Part::Two {
+            return None;
+        }
+
+        let tokens: i64 = parse::sections(input)
+            .iter()
+            .filter_map(|machine| {
+                let [x1, y1, x2, y2, r1, r2] = parse_machine(machine.text);
+                (0..=100)
+                    .flat_map(|times_a| (0..=100).map(move |times_b| (times_a, times_b)))
+                    .filter(|(times_a, times_b)| times_a * x1 + times_b * x2 == r1 && times_a * y1 + times_b * y2 == r2)
+                    .map(|(times_a, times_b)| times_a * 3 + times_b)
+                    .min()
+            })
+            .sum();
+
+        Some(tokens.to_string())
+    }
+
+    fn shrink(input: &str) -> Vec<String> {
+        gen::shrink_sections(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_machine() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400";
+        let impossible_input = "Button A: X+26, Y+66\nButton B: X+67, Y+21\nPrize: X=12748, Y=12176";
+        let possible_input2 = "Button A: X+17, Y+86\nButton B: X+84, Y+37\nPrize: X=7870, Y=6450";
+
+        assert_eq!(solve_machine(&parse_machine(input), 0), Some(280));
+        assert_eq!(solve_machine(&parse_machine(impossible_input), 0), None);
+        assert_eq!(solve_machine(&parse_machine(possible_input2), 0), Some(200));
+    }
+
+    #[test]
+    fn test_presses_are_whole_and_positive() {
+        // Solvable with 1.5 presses of A, or with a negative number of presses of B
+        let half = "Button A: X+2, Y+2\nButton B: X+1, Y+3\nPrize: X=3, Y=3";
+        let negative = "Button A: X+1, Y+1\nButton B: X+1, Y+2\nPrize: X=3, Y=2";
+
+        assert_eq!(presses(&parse_machine(half), 0), None);
+        assert_eq!(presses(&parse_machine(negative), 0), None);
+    }
+
+    #[test]
+    fn test_against_reference() {
+        let settings = gen::Settings {
+            cases: 300,
+            ..gen::Settings::default()
+        };
+
+        if let Err(error) = gen::check::<Day13>(None, &settings) {
+            panic!("{}", error);
+        }
+    }
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day13_the_claw::Day13);
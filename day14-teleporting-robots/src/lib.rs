@@ -0,0 +1,220 @@
+use core::fmt;
+
+use std::str::FromStr;
+
+use aoc::cycle;
+use aoc::parse::{self, Integers};
+use aoc::{Map, Params, Pos, Solution, SparseMap, Topology};
+
+pub struct Robot {
+    position: Pos,
+    velocity: Pos
+}
+
+impl FromStr for Robot {
+    type Err = String;
+
+    // p=0,4 v=3,-3
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Integers([position_x, position_y, velocity_x, velocity_y]) = s.parse()?;
+
+        Ok(Robot {
+            position: Pos::new(position_x as isize, position_y as isize),
+            velocity: Pos::new(velocity_x as isize, velocity_y as isize)
+        })
+    }
+}
+
+impl Robot {
+    fn position_after<T>(&self, seconds: isize, floor: &Map<T>) -> Pos {
+        floor.step(self.position, self.velocity * seconds).expect("The floor wraps around every edge")
+    }
+}
+
+pub struct Floor {
+    size: Pos,
+    robots: SparseMap<()>
+}
+
+impl Floor {
+    pub fn new(size: Pos, positions: &[Pos]) -> Self {
+        Floor { size, robots: positions.iter().map(|&pos| (pos, ())).collect() }
+    }
+}
+
+impl fmt::Display for Floor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                if self.robots.contains(Pos::new(x, y)) {
+                    write!(f, "x")?;
+                } else {
+                    write!(f, "-")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+// How big the real floor is, the example's is smaller
+const WIDE: usize = 101;
+const TALL: usize = 103;
+
+// The robots teleport to the other side of the floor when they walk off an edge. Each tile counts
+// the robots on it.
+fn floor(width: usize, height: usize) -> Map<usize> {
+    Map::new(width, height, 0).with_topology(Topology::Toroidal)
+}
+
+/// The robots and the empty floor they walk on
+pub struct Restroom {
+    robots: Vec<Robot>,
+    floor: Map<usize>,
+}
+
+/// When the robots first show a christmas tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tree {
+    After(isize),
+    /// The robots are back where they started after `period` seconds and never showed one
+    Missing { period: usize },
+}
+
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tree::After(seconds) => write!(f, "{}", seconds),
+            Tree::Missing { period } => write!(f, "no tree within period {}", period),
+        }
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Restroom Redoubt";
+
+    type Input = Restroom;
+    type Part1 = usize;
+    type Part2 = Tree;
+
+    fn parse(input: &str) -> Restroom {
+        Self::parse_with(input, &Params::default())
+    }
+
+    // The sidecar of the example sets `wide` and `tall`
+    fn parse_with(input: &str, params: &Params) -> Restroom {
+        let robots = parse::records(input).unwrap_or_else(|error| panic!("{}", error));
+        let setting = |name, default| params.get_or(name, default).unwrap_or_else(|error| panic!("{}", error));
+
+        // Puzzle x goes down the map, so the map is as wide as the floor is tall
+        Restroom { robots, floor: floor(setting("tall", TALL), setting("wide", WIDE)) }
+    }
+
+    fn part1(restroom: &Restroom) -> usize {
+        let mut floor = restroom.floor.clone();
+        for robot in &restroom.robots {
+            let position = robot.position_after(100, &floor);
+            floor[position] += 1;
+        }
+
+        // Robots on the middle lines aren't in any quadrant
+        let (middle_x, middle_y) = (floor.height as isize / 2, floor.width as isize / 2);
+        let mut ranges: [usize; 4] = [0; 4];
+        for (Pos { x, y }, &count) in floor.iter() {
+            if x != middle_x && y != middle_y {
+                ranges[(x > middle_x) as usize + 2 * (y > middle_y) as usize] += count;
+            }
+        }
+
+        ranges.iter().product()
+    }
+
+    // Let's find a christmas three. The robots end up where they started sooner or later, so if
+    // there's a tree it shows up before that.
+    fn part2(restroom: &Restroom) -> Tree {
+        let Restroom { robots, floor } = restroom;
+        let start: Vec<Pos> = robots.iter().map(|r| r.position).collect();
+        let cycle = cycle::brent(start, |positions| {
+            positions.iter().zip(robots).map(|(&pos, r)| floor.step(pos, r.velocity)).collect()
+        }).expect("The floor wraps around every edge");
+
+        let tree = (0..cycle.period as isize).find(|&steps| {
+            let positions: Vec<Pos> = robots.iter().map(|r| r.position_after(steps, floor)).collect();
+
+            // Count the maximum number of robots on 1 line
+            let &maximum_on_line = positions.iter().fold(vec![0; floor.height], |mut acc, pos| {
+                acc[pos.x as usize] += 1;
+                acc
+            }).iter().max().unwrap();
+
+            maximum_on_line >= 34
+        });
+
+        tree.map_or(Tree::Missing { period: cycle.period }, Tree::After)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::examples::parse_expected;
+
+    #[test]
+    fn test_robot_from() {
+        let robot = "p=7,6 v=-1,-3".parse::<Robot>().unwrap();
+        assert_eq!(robot.position, Pos::new(7, 6));
+        assert_eq!(robot.velocity, Pos::new(-1, -3));
+        assert_eq!("p=7,6 v=-1".parse::<Robot>().err(), Some("Expected 4 numbers, found 3".to_string()));
+    }
+
+    #[test]
+    fn test_floor_display() {
+        let positions = [Pos::new(0, 0), Pos::new(2, 1)];
+        let floor = Floor::new(Pos::new(3, 2), &positions);
+
+        assert_eq!(floor.to_string(), "x--\n--x\n");
+    }
+
+    fn example() -> Restroom {
+        let params = parse_expected(include_str!("../example.expected")).unwrap().params;
+        Day14::parse_with(include_str!("../example.txt"), &params)
+    }
+
+    #[test]
+    fn test_floor_size() {
+        let floor = example().floor;
+        assert_eq!((floor.width, floor.height), (7, 11));
+
+        // Robots near the corner don't make it the example's floor
+        let floor = Day14::parse("p=0,0 v=1,1\np=6,4 v=1,1").floor;
+        assert_eq!((floor.width, floor.height), (103, 101));
+    }
+
+    #[test]
+    fn test_no_tree() {
+        let tree = Day14::part2(&example());
+
+        assert_eq!(tree, Tree::Missing { period: 77 });
+        assert_eq!(tree.to_string(), "no tree within period 77");
+    }
+
+    #[test]
+    fn test_robot_position_after() {
+        let robot = Robot {
+            position: Pos::new(2, 4),
+            velocity: Pos::new(2, -3)
+        };
+
+        let example_grid = floor(7, 11);
+        assert_eq!(robot.position_after(1, &example_grid), Pos::new(4, 1));
+        assert_eq!(robot.position_after(2, &example_grid), Pos::new(6, 5));
+        assert_eq!(robot.position_after(3, &example_grid), Pos::new(8, 2));
+        assert_eq!(robot.position_after(4, &example_grid), Pos::new(10, 6));
+        assert_eq!(robot.position_after(5, &example_grid), Pos::new(1, 3));
+    }
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day14_teleporting_robots::Day14);
@@ -0,0 +1,300 @@
+use aoc::parse;
+use aoc::render::Rgb;
+use aoc::viz::{Cell, Frame, Style};
+use aoc::{Direction, Map, Pos, Solution};
+
+#[derive(Debug,Clone,Copy)]
+enum Tile {
+    Empty,
+    Wall,
+    Box,
+    LeftBigBox,
+    RightBigBox,
+    Robot,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Tile::Empty),
+            '#' => Ok(Tile::Wall),
+            'O' => Ok(Tile::Box),
+            '[' => Ok(Tile::LeftBigBox),
+            ']' => Ok(Tile::RightBigBox),
+            '@' => Ok(Tile::Robot),
+            _ => Err(c),
+        }
+    }
+}
+
+impl Tile {
+    fn to_char(self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::Wall => '#',
+            Tile::Box => 'O',
+            Tile::LeftBigBox => '[',
+            Tile::RightBigBox => ']',
+            Tile::Robot => '@',
+        }
+    }
+
+    fn to_cell(self) -> Cell {
+        let cell = Cell::from(self.to_char());
+        match self {
+            Tile::Wall => cell.fg(Rgb::GREY),
+            Tile::Box | Tile::LeftBigBox | Tile::RightBigBox => cell.fg(Rgb::YELLOW),
+            _ => cell,
+        }
+    }
+}
+
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    map: Map<Tile>,
+    robot: Pos,
+    directions: Vec<Direction>,
+}
+
+impl From<&str> for Puzzle {
+    fn from(input: &str) -> Self {
+        let [map_section, directions_section] = parse::exact_sections(input).unwrap_or_else(|error| panic!("{}", error));
+        let map = Map::try_parse(map_section.text).unwrap_or_else(|error| panic!("{}", error));
+        let directions = directions_section
+            .text
+            .chars()
+            .filter_map(|c| Direction::try_from(c).ok())
+            .collect();
+
+        let robot = map
+            .iter()
+            .find(|(_, tile)| matches!(tile, Tile::Robot))
+            .map(|(point, _)| point)
+            .unwrap_or_default();
+
+        Self {
+            map,
+            robot,
+            directions,
+        }
+    }
+}
+
+impl Puzzle {
+    fn expanded_clone(&self) -> Puzzle {
+        let map = self.map.scale(1, 2, |tile| match tile {
+            Tile::Empty => vec![Tile::Empty, Tile::Empty],
+            Tile::Wall => vec![Tile::Wall, Tile::Wall],
+            Tile::Box => vec![Tile::LeftBigBox, Tile::RightBigBox],
+            Tile::Robot => vec![Tile::Robot, Tile::Empty],
+            _ => panic!("Can't expand tile: {:?}", tile),
+        });
+
+        let robot = map
+            .iter()
+            .find(|(_, tile)| matches!(tile, Tile::Robot))
+            .map(|(point, _)| point)
+            .unwrap();
+
+        Puzzle {
+            map,
+            robot,
+            directions: self.directions.clone(),
+        }
+    }
+
+    fn step(&mut self, direction: Direction) {
+        let movement = direction.delta();
+
+        let mut next_pos = self.robot + movement;
+        let next_robot_pos = next_pos;
+        match self.map.at_point(next_pos) {
+            Some(Tile::Empty) => {
+                self.map.set_point(self.robot, Tile::Empty);
+                self.map.set_point(next_pos, Tile::Robot);
+                self.robot = next_robot_pos;
+            }
+            Some(Tile::Box) => loop {
+                next_pos += movement;
+
+                match self.map.at_point(next_pos) {
+                    Some(Tile::Empty) => {
+                        self.map.set_point(self.robot, Tile::Empty);
+                        self.map.set_point(next_robot_pos, Tile::Robot);
+                        self.map.set_point(next_pos, Tile::Box);
+                        self.robot = next_robot_pos;
+                        break;
+                    }
+                    Some(Tile::Wall) | None => break,
+                    _ => {}
+                }
+            },
+            Some(tile @ Tile::LeftBigBox) | Some(tile @ Tile::RightBigBox) => {
+                // Going horizontal is easy
+                match direction {
+                    Direction::West | Direction::East => {
+                        let mut positions = vec![];
+                        loop {
+                            next_pos += movement;
+
+                            match self.map.at_point(next_pos) {
+                                Some(Tile::LeftBigBox) | Some(Tile::RightBigBox) => {
+                                    positions.push(next_pos);
+                                }
+                                Some(Tile::Empty) => {
+                                    self.map.set_point(self.robot, Tile::Empty);
+                                    self.map.set_point(next_robot_pos, Tile::Robot);
+                                    self.map.set_point(next_pos, if matches!(direction, Direction::West) { Tile::LeftBigBox } else { Tile::RightBigBox });
+                                    self.robot = next_robot_pos;
+                                    for pos in positions {
+                                        match self.map.at_point(pos) {
+                                            Some(Tile::LeftBigBox) => {
+                                                self.map.set_point(pos, Tile::RightBigBox);
+                                            }
+                                            Some(Tile::RightBigBox) => {
+                                                self.map.set_point(pos, Tile::LeftBigBox);
+                                            }
+                                            _ => {}
+                                        }
+                                    }
+                                    break;
+                                }
+                                Some(Tile::Wall) | None => break,
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {
+                        // Up/Down is a bit more complicated since we need to resolve both left & right
+                        let mut y_to_check: Vec<isize> = vec![];
+                        let mut boxes_to_move: Vec<Vec<isize>> = vec![];
+
+                        if matches!(tile, Tile::RightBigBox) {
+                            y_to_check.extend([next_robot_pos.y - 1, next_robot_pos.y]);
+                        } else {
+                            y_to_check.extend([next_robot_pos.y, next_robot_pos.y + 1]);
+                        }
+
+                        loop {
+                            boxes_to_move.push(y_to_check.clone());
+
+                            next_pos += movement;
+                            let next_tiles: Vec<(Pos, &Tile)> = y_to_check.iter()
+                                .map(|&y| Pos::new(next_pos.x, y))
+                                .map(|pos| (pos, self.map.at_point(pos).unwrap())).collect();
+
+                            if next_tiles.iter().all(|(_, tile)| matches!(tile, Tile::Empty)) {
+                                let mut changes: std::collections::HashMap<Pos, Tile> = std::collections::HashMap::new();
+
+                                boxes_to_move.iter().enumerate().for_each(|(i, y)| {
+                                    y.iter().for_each(|&y| {
+                                        let current_pos = Pos::new(next_robot_pos.x, y) + movement * i as isize;
+                                        let destination_pos = current_pos + movement;
+                                        let tile = self.map.at_point(current_pos).unwrap();
+
+                                        changes.insert(destination_pos, *tile);
+                                        changes.entry(current_pos).or_insert(Tile::Empty);
+                                    });
+                                });
+
+                                for (pos, tile) in changes.iter() {
+                                    self.map.set_point(*pos, *tile);
+                                }
+
+                                self.map.set_point(self.robot, Tile::Empty);
+                                self.map.set_point(next_robot_pos, Tile::Robot);
+                                self.robot = next_robot_pos;
+                                break;
+                            } else if next_tiles.iter().any(|(_, tile)| matches!(tile, Tile::Wall)) {
+                                break;
+                            } else {
+                                y_to_check.clear();
+                                // Find out which boxes we need to check next
+                                for (i, (Pos { y: x, .. }, tile)) in next_tiles.iter().enumerate() {
+                                    if matches!(tile, Tile::RightBigBox) && i == 0 {
+                                        y_to_check.extend([*x - 1, *x]);
+                                    } else if matches!(tile, Tile::LeftBigBox) && i == next_tiles.len() - 1 {
+                                        y_to_check.extend([*x, *x + 1]);
+                                    } else if matches!(tile, Tile::RightBigBox) || matches!(tile, Tile::LeftBigBox) {
+                                        y_to_check.push(*x);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn resolve(&mut self) {
+        for i in 0..self.directions.len() {
+            self.step(self.directions[i]);
+        }
+    }
+
+    fn frame(&self, status: String) -> Frame {
+        let mut frame = Frame::from_map(&self.map, |tile| tile.to_cell()).status(status);
+        frame.highlight([self.robot], Style::ACTOR);
+        frame
+    }
+
+    fn score(&self) -> usize {
+        self.map
+            .iter()
+            .filter(|(_, tile)| matches!(tile, Tile::Box) || matches!(tile, Tile::LeftBigBox))
+            .map(|(pos, _)| (pos.x * 100 + pos.y) as usize)
+            .sum()
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Warehouse Woes";
+
+    type Input = Puzzle;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Puzzle {
+        Puzzle::from(input)
+    }
+
+    fn part1(puzzle: &Puzzle) -> usize {
+        let mut puzzle = puzzle.clone();
+        puzzle.resolve();
+        puzzle.score()
+    }
+
+    fn part2(puzzle: &Puzzle) -> usize {
+        let mut expanded_puzzle = puzzle.expanded_clone();
+        expanded_puzzle.resolve();
+        expanded_puzzle.score()
+    }
+
+    // The robot's moves in the normal warehouse, then in the expanded one
+    fn watch(puzzle: &Puzzle, show: &mut dyn FnMut(&Frame)) -> Result<(), String> {
+        for (part, mut puzzle) in [(1, puzzle.clone()), (2, puzzle.expanded_clone())] {
+            let moves = puzzle.directions.clone();
+            show(&puzzle.frame(format!("Part {}: start", part)));
+
+            for (i, &direction) in moves.iter().enumerate() {
+                puzzle.step(direction);
+                show(&puzzle.frame(format!("Part {}: move {}/{} {}", part, i + 1, moves.len(), direction)));
+            }
+        }
+
+        Ok(())
+    }
+}
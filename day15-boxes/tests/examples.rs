@@ -0,0 +1 @@
+aoc::example_tests!(day15_boxes::Day15);
@@ -0,0 +1,257 @@
+use aoc::gen::Rng;
+use aoc::search::dijkstra;
+use aoc::{Direction, Map, Part, Pos, Solution, Walker};
+use std::fmt;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Wall,
+    Corridor,
+    Start,
+    End,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Tile::Wall),
+            '.' => Ok(Tile::Corridor),
+            'S' => Ok(Tile::Start),
+            'E' => Ok(Tile::End),
+            _ => Err(c),
+        }
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Tile::Wall => '#',
+            Tile::Corridor => '.',
+            Tile::Start => 'S',
+            Tile::End => 'E',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+pub struct Maze {
+    map: Map<Tile>,
+    position: Walker,
+    end: Pos,
+}
+
+impl From<&str> for Maze {
+    fn from(s: &str) -> Self {
+        let map = Map::try_parse(s).unwrap_or_else(|error| panic!("{}", error));
+
+        let find = |wanted: Tile| map.iter().find(|&(_, &tile)| tile == wanted).map(|(pos, _)| pos);
+        let start = find(Tile::Start).expect("The maze has no start");
+        let end = find(Tile::End).expect("The maze has no end");
+        let position = Walker::new(start, Direction::East);
+
+        Maze { map, position, end }
+    }
+}
+
+impl Maze {
+    // Returns the cost of the cheapest path, and the number of tiles on any of the cheapest paths
+    fn cheapest_path(&self) -> (u32, usize) {
+        let search = dijkstra(
+            [self.position],
+            |&reindeer| {
+                let mut moves = vec![];
+
+                if matches!(reindeer.peek(&self.map), Some(Tile::Corridor | Tile::Start | Tile::End)) {
+                    moves.push((Walker::new(reindeer.ahead(), reindeer.heading), 1));
+                }
+
+                for turn in [reindeer.heading.turn_left(), reindeer.heading.turn_right()] {
+                    moves.push((Walker::new(reindeer.pos, turn), 1000));
+                }
+
+                moves
+            },
+            |reindeer| reindeer.pos == self.end,
+        );
+
+        let tiles: HashSet<Pos> = search
+            .goals()
+            .iter()
+            .flat_map(|goal| search.states_on_paths_to(goal))
+            .map(|reindeer| reindeer.pos)
+            .collect();
+
+        (search.goal_cost().unwrap(), tiles.len())
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Reindeer Maze";
+
+    type Input = Maze;
+    type Part1 = u32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Maze {
+        Maze::from(input)
+    }
+
+    fn part1(maze: &Maze) -> u32 {
+        maze.cheapest_path().0
+    }
+
+    fn part2(maze: &Maze) -> usize {
+        maze.cheapest_path().1
+    }
+
+    // A walled maze with the start and end anywhere inside, and a way between them
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let (height, width) = (4 + rng.below(size + 1), 4 + rng.below(size + 1));
+
+        loop {
+            let mut rows: Vec<Vec<char>> = (0..height)
+                .map(|x| {
+                    (0..width)
+                        .map(|y| {
+                            let border = x == 0 || y == 0 || x == height - 1 || y == width - 1;
+                            if border || rng.chance(0.3) { '#' } else { '.' }
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let mut inside = || (1 + rng.below(height - 2), 1 + rng.below(width - 2));
+            let (start, end) = (inside(), inside());
+            if start == end {
+                continue;
+            }
+            rows[start.0][start.1] = 'S';
+            rows[end.0][end.1] = 'E';
+
+            let input: Vec<String> = rows.into_iter().map(|row| row.into_iter().collect()).collect();
+            let input = input.join("\n");
+            if Day16::reference(&input, Part::One).is_some() {
+                return Some(input);
+            }
+        }
+    }
+
+    // Relaxes every move until nothing changes, from the start and backwards from the end. The best
+    // tiles are the ones where the two add up to the cheapest path.
+    fn reference(input: &str, part: Part) -> Option<String> {
+        let map: Map<Tile> = Map::try_parse(input).ok()?;
+        let open = |pos: Pos| matches!(map.at_point(pos), Some(Tile::Corridor | Tile::Start | Tile::End));
+        let moves = |reindeer: Walker| {
+            let mut moves = vec![(Walker::new(reindeer.pos, reindeer.heading.turn_left()), 1000)];
+            moves.push((Walker::new(reindeer.pos, reindeer.heading.turn_right()), 1000));
+            if open(reindeer.ahead()) {
+                moves.push((Walker::new(reindeer.ahead(), reindeer.heading), 1));
+            }
+            moves
+        };
+
+        let states: Vec<Walker> = map
+            .iter()
+            .filter(|&(pos, _)| open(pos))
+            .flat_map(|(pos, _)| Direction::CARDINALS.map(|heading| Walker::new(pos, heading)))
+            .collect();
+        let start = map.iter().find(|&(_, &tile)| tile == Tile::Start)?.0;
+        let end = map.iter().find(|&(_, &tile)| tile == Tile::End)?.0;
+
+        let mut from_start: HashMap<Walker, u32> = HashMap::from([(Walker::new(start, Direction::East), 0)]);
+        let mut to_end: HashMap<Walker, u32> =
+            Direction::CARDINALS.iter().map(|&heading| (Walker::new(end, heading), 0)).collect();
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &state in &states {
+                for (next, cost) in moves(state) {
+                    if let Some(&cost_here) = from_start.get(&state) {
+                        if from_start.get(&next).is_none_or(|&known| cost_here + cost < known) {
+                            from_start.insert(next, cost_here + cost);
+                            changed = true;
+                        }
+                    }
+                    if let Some(&cost_there) = to_end.get(&next) {
+                        if to_end.get(&state).is_none_or(|&known| cost + cost_there < known) {
+                            to_end.insert(state, cost + cost_there);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        let best = Direction::CARDINALS
+            .iter()
+            .filter_map(|&heading| from_start.get(&Walker::new(end, heading)))
+            .min()?;
+        let answer = match part {
+            Part::One => best.to_string(),
+            Part::Two => {
+                let tiles: HashSet<Pos> = states
+                    .iter()
+                    .filter(|state| {
+                        matches!((from_start.get(state), to_end.get(state)), (Some(a), Some(b)) if a + b == *best)
+                    })
+                    .map(|state| state.pos)
+                    .collect();
+                tiles.len().to_string()
+            }
+        };
+
+        Some(answer)
+    }
+
+    // Wall off one more corridor tile
+    fn shrink(input: &str) -> Vec<String> {
+        input
+            .char_indices()
+            .filter(|&(_, c)| c == '.')
+            .map(|(index, _)| format!("{}#{}", &input[..index], &input[index + 1..]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cheapest_path() {
+        let maze = Maze::from(include_str!("../example.txt").trim_end());
+        assert_eq!(maze.cheapest_path(), (7036, 45));
+
+        let maze = Maze::from(include_str!("../example2.txt").trim_end());
+        assert_eq!(maze.cheapest_path(), (11048, 64));
+    }
+
+    #[test]
+    fn test_start_and_end_anywhere() {
+        let maze = Maze::from("#####\n#.E.#\n#.#.#\n#..S#\n#####");
+
+        // North to the top row, then west onto the end
+        assert_eq!(maze.cheapest_path(), (2003, 4));
+    }
+
+    #[test]
+    fn test_against_reference() {
+        let settings = aoc::gen::Settings {
+            cases: 300,
+            max_size: 10,
+            ..aoc::gen::Settings::default()
+        };
+
+        if let Err(error) = aoc::gen::check::<Day16>(None, &settings) {
+            panic!("{}", error);
+        }
+    }
+}
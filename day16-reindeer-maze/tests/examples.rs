@@ -0,0 +1 @@
+aoc::example_tests!(day16_reindeer_maze::Day16);
@@ -0,0 +1,213 @@
+//! Programs as text, one instruction per line:
+//!
+//! ```text
+//! # Print A in octal, lowest digit first
+//! l0:
+//!     bst A
+//!     out B
+//!     adv 3
+//!     jnz l0
+//! ```
+//!
+//! Combo operands are written `0` to `3`, `A`, `B`, `C`, or `reserved` for the operand 7 the
+//! computer doesn't accept. `bxc` ignores its operand, which can be left out when it's 0. `jnz`
+//! jumps to a label or a literal instruction index. Comments start with `#`.
+
+use std::collections::{HashMap, HashSet};
+
+use aoc::parse::ParseError;
+
+use crate::{ADV, BDV, BST, BXC, CDV, JNZ, OUT};
+
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+const COMBO_OPERANDS: [&str; 8] = ["0", "1", "2", "3", "A", "B", "C", "reserved"];
+
+pub(crate) fn takes_combo(opcode: u8) -> bool {
+    matches!(opcode, ADV | BST | OUT | BDV | CDV)
+}
+
+fn label(index: u8) -> String {
+    format!("l{}", index)
+}
+
+/// One instruction as text, like `bst A`, without labels. Both numbers need to be 3-bit.
+pub fn instruction_text(opcode: u8, operand: u8) -> String {
+    let mnemonic = MNEMONICS[opcode as usize];
+    match opcode {
+        BXC if operand == 0 => mnemonic.to_string(),
+        _ if takes_combo(opcode) => format!("{} {}", mnemonic, COMBO_OPERANDS[operand as usize]),
+        _ => format!("{} {}", mnemonic, operand),
+    }
+}
+
+/// The text form of a program. Every program of 3-bit numbers in pairs has one, and
+/// `assemble` turns it back into the same numbers.
+pub fn disassemble(program: &[u8]) -> Result<String, String> {
+    if !program.len().is_multiple_of(2) {
+        return Err(format!("A program has instructions in pairs, not {} numbers", program.len()));
+    }
+    if let Some(number) = program.iter().find(|&&number| number > 7) {
+        return Err(format!("{} isn't a 3-bit number", number));
+    }
+
+    // Jumps to an opcode get a label, jumps between an opcode and its operand or past the end
+    // stay numbers
+    let targets: HashSet<u8> = program
+        .chunks(2)
+        .filter(|instruction| instruction[0] == JNZ)
+        .map(|instruction| instruction[1])
+        .filter(|&target| target.is_multiple_of(2) && (target as usize) < program.len())
+        .collect();
+
+    let mut text = String::new();
+    for (index, instruction) in (0..).step_by(2).zip(program.chunks(2)) {
+        if targets.contains(&index) {
+            text += &format!("{}:\n", label(index));
+        }
+
+        let (opcode, operand) = (instruction[0], instruction[1]);
+        if opcode == JNZ && targets.contains(&operand) {
+            text += &format!("    jnz {}\n", label(operand));
+        } else {
+            text += &format!("    {}\n", instruction_text(opcode, operand));
+        }
+    }
+
+    Ok(text)
+}
+
+/// The program written in `source`, see the module documentation for the syntax
+pub fn assemble(source: &str) -> Result<Vec<u8>, ParseError> {
+    let error = |line: usize, message: String| ParseError { line, message };
+
+    // Labels can be used before they're defined, so find them all first
+    let mut labels: HashMap<&str, u8> = HashMap::new();
+    let mut instructions = vec![];
+    for (line, text) in (1..).zip(source.lines()) {
+        let mut code = text.split('#').next().unwrap_or_default().trim();
+
+        if let Some((name, rest)) = code.split_once(':') {
+            let name = name.trim();
+            let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(error(line, format!("`{}` isn't a label name", name)));
+            }
+            let index = 2 * instructions.len();
+            if index > 7 {
+                return Err(error(line, format!("Label `{}` is at {}, past where jnz can jump", name, index)));
+            }
+            if labels.insert(name, index as u8).is_some() {
+                return Err(error(line, format!("Label `{}` is defined twice", name)));
+            }
+            code = rest.trim();
+        }
+
+        if !code.is_empty() {
+            instructions.push((line, code));
+        }
+    }
+
+    let mut program = vec![];
+    for (line, code) in instructions {
+        let mut words = code.split_whitespace();
+        let mnemonic = words.next().unwrap_or_default();
+        let operand = words.next();
+        if words.next().is_some() {
+            return Err(error(line, format!("`{}` has more than one operand", code)));
+        }
+
+        let opcode = MNEMONICS
+            .iter()
+            .position(|&known| known.eq_ignore_ascii_case(mnemonic))
+            .ok_or_else(|| error(line, format!("`{}` isn't an instruction", mnemonic)))? as u8;
+        let literal = |text: &str| {
+            text.parse::<u8>()
+                .ok()
+                .filter(|&number| number <= 7)
+                .ok_or_else(|| error(line, format!("`{}` isn't a number from 0 to 7", text)))
+        };
+
+        let operand = match (opcode, operand) {
+            (BXC, None) => 0,
+            (_, None) => return Err(error(line, format!("`{}` needs an operand", mnemonic))),
+            (JNZ, Some(target)) => match labels.get(target) {
+                Some(&index) => index,
+                None if target.starts_with(|c: char| c.is_ascii_digit()) => literal(target)?,
+                None => return Err(error(line, format!("There's no label `{}`", target))),
+            },
+            (_, Some(text)) if takes_combo(opcode) => COMBO_OPERANDS
+                .iter()
+                .position(|&known| known.eq_ignore_ascii_case(text))
+                .ok_or_else(|| error(line, format!("`{}` isn't a combo operand, use 0 to 3, A, B or C", text)))?
+                as u8,
+            (_, Some(text)) => literal(text)?,
+        };
+
+        program.extend([opcode, operand]);
+    }
+
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::gen::Rng;
+
+    #[test]
+    fn test_disassemble() {
+        let program = [2, 4, 1, 3, 7, 5, 4, 1, 1, 3, 0, 3, 5, 5, 3, 0];
+        let expected = "\
+l0:
+    bst A
+    bxl 3
+    cdv B
+    bxc 1
+    bxl 3
+    adv 3
+    out B
+    jnz l0
+";
+
+        assert_eq!(disassemble(&program).unwrap(), expected);
+        assert_eq!(disassemble(&[4, 0, 3, 7, 0, 7]).unwrap(), "    bxc\n    jnz 7\n    adv reserved\n");
+        assert!(disassemble(&[0, 1, 5]).is_err());
+        assert!(disassemble(&[0, 8]).is_err());
+    }
+
+    #[test]
+    fn test_assemble() {
+        let source = "
+            # A made-up program
+            jnz start   # skip the first out when A isn't 0
+            out 0
+            start: out A
+            ADV 1 ; ignored
+        ";
+        let error = assemble(source).unwrap_err();
+        assert_eq!(error.to_string(), "Line 6: `ADV 1 ; ignored` has more than one operand");
+
+        let source = source.replace(" ; ignored", "");
+        assert_eq!(assemble(&source).unwrap(), [3, 4, 5, 0, 5, 4, 0, 1]);
+
+        assert_eq!(assemble("jnz nowhere").unwrap_err().message, "There's no label `nowhere`");
+        assert_eq!(assemble("bxl 8").unwrap_err().message, "`8` isn't a number from 0 to 7");
+        assert_eq!(assemble("out 4").unwrap_err().message, "`4` isn't a combo operand, use 0 to 3, A, B or C");
+        assert_eq!(assemble("out").unwrap_err().message, "`out` needs an operand");
+        assert_eq!(assemble("a:\na:").unwrap_err().message, "Label `a` is defined twice");
+        assert_eq!(assemble("bxc\nbxc\nbxc\nbxc\nend:").unwrap_err().line, 5);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = Rng::new(17);
+        for _ in 0..1000 {
+            let length = 2 * rng.below(10);
+            let program: Vec<u8> = (0..length).map(|_| rng.below(8) as u8).collect();
+
+            let text = disassemble(&program).unwrap();
+            assert_eq!(assemble(&text).unwrap(), program, "{}", text);
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Programs turned into a closure per instruction, with the operands worked out up front, to try
+//! lots of values of `A` quickly
+
+use std::ops::Range;
+
+use rayon::prelude::*;
+
+use crate::{asm, FaultKind, Register, A, ADV, B, BDV, BST, BXC, BXL, C, CDV, JNZ, OUT};
+
+/// What the computer does after an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Effect {
+    Next,
+    Jump(usize),
+    Print(u8),
+    Fault(FaultKind),
+}
+
+type Instruction = Box<dyn Fn(&mut [u64; 3]) -> Effect + Send + Sync>;
+
+/// Why a compiled program stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// It ran off the end of the program
+    Halted,
+    /// The output callback asked to stop
+    Stopped,
+    /// It ran as many instructions as the step limit allows
+    StepLimit,
+    /// Like the interpreter, it can't divide by powers of 2 that don't fit in a register
+    Fault(FaultKind),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Combo {
+    Literal(u64),
+    Register(usize),
+}
+
+// Reads the combo operand the way that fits it, so a literal doesn't look at the registers
+fn with_combo<F>(combo: Combo, instruction: F) -> Instruction
+where
+    F: Fn(&mut [u64; 3], u64) -> Effect + Send + Sync + 'static,
+{
+    match combo {
+        Combo::Literal(value) => Box::new(move |registers| instruction(registers, value)),
+        Combo::Register(register) => Box::new(move |registers| {
+            let value = registers[register];
+            instruction(registers, value)
+        }),
+    }
+}
+
+// Set `register` to A divided by 2 to the power of `shift`, like `adv`, `bdv` and `cdv` do
+fn divide_into(registers: &mut [u64; 3], register: usize, shift: u64) -> Effect {
+    match registers[A].divide(&shift) {
+        Some(value) => {
+            registers[register] = value;
+            Effect::Next
+        }
+        None => Effect::Fault(FaultKind::ShiftOverflow),
+    }
+}
+
+fn compile_instruction(opcode: u8, operand: u8) -> Result<Instruction, String> {
+    // Like the interpreter, only 3-bit numbers are instructions
+    if opcode > 7 {
+        return Err(format!("{} isn't an opcode", opcode));
+    }
+    if operand > 7 {
+        return Err(format!("{} isn't an operand", operand));
+    }
+    let text = || asm::instruction_text(opcode, operand);
+    let combo = match operand {
+        0..=3 => Combo::Literal(operand as u64),
+        4..=6 => Combo::Register(operand as usize - 4),
+        _ if asm::takes_combo(opcode) => return Err(format!("`{}` uses the reserved combo operand", text())),
+        _ => Combo::Literal(operand as u64),
+    };
+    let literal = operand as u64;
+
+    let instruction: Instruction = match opcode {
+        ADV => with_combo(combo, |registers, shift| divide_into(registers, A, shift)),
+        BXL => Box::new(move |registers| {
+            registers[B] ^= literal;
+            Effect::Next
+        }),
+        BST => with_combo(combo, |registers, value| {
+            registers[B] = value % 8;
+            Effect::Next
+        }),
+        JNZ if operand % 2 == 1 => return Err(format!("`{}` jumps between an opcode and its operand", text())),
+        JNZ => {
+            let target = operand as usize / 2;
+            Box::new(move |registers| if registers[A] != 0 { Effect::Jump(target) } else { Effect::Next })
+        }
+        BXC => Box::new(|registers| {
+            registers[B] ^= registers[C];
+            Effect::Next
+        }),
+        OUT => with_combo(combo, |_, value| Effect::Print((value % 8) as u8)),
+        BDV => with_combo(combo, |registers, shift| divide_into(registers, B, shift)),
+        CDV => with_combo(combo, |registers, shift| divide_into(registers, C, shift)),
+        _ => unreachable!("Opcodes are 3-bit numbers"),
+    };
+
+    Ok(instruction)
+}
+
+/// How many instructions a compiled program runs before it's stopped, unless it's given another
+/// limit. Real programs print their output in a few hundred.
+pub const STEP_LIMIT: usize = 1_000_000;
+
+/// A compiled program. Running it doesn't allocate, and it can run on many threads at once.
+pub struct Compiled {
+    instructions: Vec<Instruction>,
+    step_limit: usize,
+}
+
+impl Compiled {
+    /// Compile a program of 3-bit numbers in pairs. Programs that jump between an opcode and its
+    /// operand or use the reserved combo operand aren't supported.
+    pub fn new(program: &[u8]) -> Result<Self, String> {
+        if !program.len().is_multiple_of(2) {
+            return Err(format!("A program has instructions in pairs, not {} numbers", program.len()));
+        }
+        let instructions = program
+            .chunks(2)
+            .map(|instruction| compile_instruction(instruction[0], instruction[1]))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Compiled {
+            instructions,
+            step_limit: STEP_LIMIT,
+        })
+    }
+
+    /// Stop programs after this many instructions instead of `STEP_LIMIT`. Programs that loop
+    /// forever stop either way.
+    pub fn with_step_limit(mut self, step_limit: usize) -> Self {
+        self.step_limit = step_limit;
+        self
+    }
+
+    /// Run the program, handing every number it prints to `print`, which returns whether to go on
+    pub fn run(&self, mut registers: [u64; 3], mut print: impl FnMut(u8) -> bool) -> Exit {
+        let mut index = 0;
+        let mut steps = 0;
+        while let Some(instruction) = self.instructions.get(index) {
+            if steps >= self.step_limit {
+                return Exit::StepLimit;
+            }
+            steps += 1;
+
+            match instruction(&mut registers) {
+                Effect::Next => index += 1,
+                Effect::Jump(target) => index = target,
+                Effect::Print(value) => {
+                    if !print(value) {
+                        return Exit::Stopped;
+                    }
+                    index += 1;
+                }
+                Effect::Fault(kind) => return Exit::Fault(kind),
+            }
+        }
+
+        Exit::Halted
+    }
+
+    /// Everything the program prints, or `None` when it hits the step limit or faults
+    pub fn output(&self, registers: [u64; 3]) -> Option<Vec<u8>> {
+        let mut output = vec![];
+        let exit = self.run(registers, |value| {
+            output.push(value);
+            true
+        });
+
+        (exit == Exit::Halted).then_some(output)
+    }
+
+    /// Whether the program prints exactly `target`, stopping at the first number that's wrong
+    pub fn prints(&self, registers: [u64; 3], target: &[u8]) -> bool {
+        let mut printed = 0;
+        let exit = self.run(registers, |value| {
+            let expected = target.get(printed) == Some(&value);
+            printed += 1;
+            expected
+        });
+
+        exit == Exit::Halted && printed == target.len()
+    }
+
+    /// The smallest `A` from `candidates` that prints `target`, with `B` and `C` from `registers`.
+    /// The candidates are tried in parallel, and one that hits the step limit doesn't match.
+    pub fn brute_force(&self, registers: [u64; 3], candidates: Range<u64>, target: &[u8]) -> Option<u64> {
+        candidates.into_par_iter().find_first(|&a| self.prints([a, registers[B], registers[C]], target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solve::find_register_a;
+    use crate::Computer;
+    use aoc::gen::Rng;
+
+    fn interpret(program: &[u8], registers: [u64; 3]) -> Vec<u8> {
+        let mut computer = Computer::new(registers, program.to_vec());
+        computer.run().unwrap();
+        computer.output
+    }
+
+    #[test]
+    fn test_same_as_interpreter() {
+        let quine = [2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 5, 5, 0, 3, 3, 0];
+        let compiled = Compiled::new(&quine).unwrap();
+        let mut rng = Rng::new(17);
+        for _ in 0..1000 {
+            let registers = [rng.next_u64(), rng.next_u64(), rng.next_u64()];
+            assert_eq!(compiled.output(registers).unwrap(), interpret(&quine, registers));
+        }
+
+        // Random loops that shift A by a literal, so they end
+        for _ in 0..1000 {
+            let mut program = vec![];
+            for _ in 0..rng.below(6) {
+                let opcode = *rng.pick(&[ADV, BXL, BST, BXC, OUT, BDV, CDV]);
+                let operand = match opcode {
+                    ADV | BDV | CDV => rng.below(4) as u8,
+                    _ => rng.below(7) as u8,
+                };
+                program.extend([opcode, operand]);
+            }
+            program.extend([ADV, 1 + rng.below(3) as u8, JNZ, 0]);
+
+            let registers = [rng.next_u64(), rng.below(1000) as u64, rng.below(1000) as u64];
+            let compiled = Compiled::new(&program).unwrap();
+            assert_eq!(compiled.output(registers).unwrap(), interpret(&program, registers), "{:?}", program);
+        }
+    }
+
+    #[test]
+    fn test_brute_force() {
+        let program = [0, 3, 5, 4, 3, 0];
+        let compiled = Compiled::new(&program).unwrap();
+
+        let found = compiled.brute_force([2024, 0, 0], 0..1_000_000, &program);
+        assert_eq!(found, Some(117440));
+        assert_eq!(found, find_register_a(&program, &program).unwrap().first().copied());
+        assert_eq!(compiled.brute_force([0, 0, 0], 0..117440, &program), None);
+
+        // Prints A and then jumps to itself until A is 0, so every multiple of 8 loops forever
+        let looping = Compiled::new(&[5, 4, 3, 4, 3, 4]).unwrap();
+        assert_eq!(looping.run([8, 0, 0], |_| true), Exit::StepLimit);
+        assert_eq!(looping.brute_force([0, 0, 0], 1..100, &[0]), None);
+        assert_eq!(looping.brute_force([0, 0, 0], 0..100, &[0]), Some(0));
+    }
+
+    #[test]
+    fn test_limits_and_errors() {
+        let forever = Compiled::new(&[3, 0]).unwrap().with_step_limit(100);
+        assert_eq!(forever.run([1, 0, 0], |_| true), Exit::StepLimit);
+        assert_eq!(forever.output([0, 0, 0]), Some(vec![]));
+
+        let counter = Compiled::new(&[5, 4, 0, 1, 3, 0]).unwrap();
+        assert_eq!(counter.run([u64::MAX, 0, 0], |value| value != 3), Exit::Stopped);
+        assert!(!counter.prints([6, 0, 0], &[6, 3]));
+        assert!(counter.prints([6, 0, 0], &[6, 3, 1]));
+
+        assert_eq!(Compiled::new(&[3, 1]).err().unwrap(), "`jnz 1` jumps between an opcode and its operand");
+        assert_eq!(Compiled::new(&[5, 7]).err().unwrap(), "`out reserved` uses the reserved combo operand");
+        assert!(Compiled::new(&[5]).is_err());
+        assert_eq!(Compiled::new(&[5, 8]).err().unwrap(), "8 isn't an operand");
+        assert_eq!(Compiled::new(&[1, 9]).err().unwrap(), "9 isn't an operand");
+        assert_eq!(Compiled::new(&[8, 0]).err().unwrap(), "8 isn't an opcode");
+
+        let wide = Compiled::new(&[1, 7, 7, 5, 5, 6]).unwrap();
+        assert_eq!(wide.run([0, 64, 0], |_| true), Exit::Fault(FaultKind::ShiftOverflow));
+    }
+}
@@ -0,0 +1,273 @@
+//! Running a program an instruction at a time, to see why it doesn't output what it should
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::{asm, Computer, Fault};
+
+const REGISTER_NAMES: [char; 3] = ['A', 'B', 'C'];
+
+/// Why the debugger handed control back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// One instruction ran and nothing else happened
+    Stepped,
+    /// The instruction index ran off the end of the program
+    Halted,
+    /// The instruction at this index is next
+    Breakpoint(usize),
+    /// The last instruction changed a watched register
+    Watch { register: usize, before: u64, after: u64 },
+    /// This many instructions ran, which is the limit
+    Limit(usize),
+    /// The computer can't run the next instruction
+    Fault(Fault),
+}
+
+/// One instruction that ran
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    pub instruction_index: usize,
+    pub opcode: u8,
+    pub operand: u8,
+    pub before: [u64; 3],
+    pub after: [u64; 3],
+    /// What an `out` instruction printed
+    pub output: Option<u8>,
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let registers = |values: [u64; 3]| -> String {
+            let named: Vec<String> =
+                REGISTER_NAMES.iter().zip(values).map(|(name, value)| format!("{}={}", name, value)).collect();
+            named.join(" ")
+        };
+
+        write!(
+            f,
+            "{:>3}  {:<8} {} -> {}",
+            self.instruction_index,
+            asm::instruction_text(self.opcode, self.operand),
+            registers(self.before),
+            registers(self.after)
+        )?;
+        if let Some(output) = self.output {
+            write!(f, "  out {}", output)?;
+        }
+        Ok(())
+    }
+}
+
+/// A computer with breakpoints, register watches and an instruction limit, which remembers every
+/// instruction it runs
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    computer: Computer,
+    breakpoints: BTreeSet<usize>,
+    watches: [bool; 3],
+    limit: Option<usize>,
+    trace: Vec<TraceStep>,
+}
+
+impl Debugger {
+    pub fn new(computer: Computer) -> Self {
+        Debugger {
+            computer,
+            breakpoints: BTreeSet::new(),
+            watches: [false; 3],
+            limit: None,
+            trace: vec![],
+        }
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn trace(&self) -> &[TraceStep] {
+        &self.trace
+    }
+
+    /// Stop before running the instruction at `instruction_index`
+    pub fn add_breakpoint(&mut self, instruction_index: usize) {
+        self.breakpoints.insert(instruction_index);
+    }
+
+    pub fn remove_breakpoint(&mut self, instruction_index: usize) {
+        self.breakpoints.remove(&instruction_index);
+    }
+
+    /// Stop after an instruction changes `register`, one of `A`, `B` or `C`
+    pub fn watch(&mut self, register: usize) {
+        self.watches[register] = true;
+    }
+
+    pub fn unwatch(&mut self, register: usize) {
+        self.watches[register] = false;
+    }
+
+    /// Stop once this many instructions ran in total, so programs that loop forever stop too
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    /// Run the next instruction
+    pub fn step(&mut self) -> Stop {
+        if let Some(limit) = self.limit.filter(|&limit| self.trace.len() >= limit) {
+            return Stop::Limit(limit);
+        }
+        let (opcode, operand) = match self.computer.current() {
+            Ok(Some(instruction)) => instruction,
+            Ok(None) => return Stop::Halted,
+            Err(kind) => return Stop::Fault(self.computer.fault(kind)),
+        };
+
+        let instruction_index = self.computer.instruction_index;
+        let before = self.computer.registers;
+        let printed = self.computer.output.len();
+        match self.computer.step(opcode, operand) {
+            Ok(next) => self.computer.instruction_index = next,
+            Err(kind) => return Stop::Fault(self.computer.fault(kind)),
+        }
+        let after = self.computer.registers;
+
+        self.trace.push(TraceStep {
+            instruction_index,
+            opcode,
+            operand,
+            before,
+            after,
+            output: self.computer.output.get(printed).copied(),
+        });
+
+        let changed = (0..3).find(|&register| self.watches[register] && before[register] != after[register]);
+        match changed {
+            Some(register) => Stop::Watch {
+                register,
+                before: before[register],
+                after: after[register],
+            },
+            None => Stop::Stepped,
+        }
+    }
+
+    /// Run until something stops the computer. This runs at least one instruction, so it moves on
+    /// from the breakpoint it last stopped at.
+    pub fn resume(&mut self) -> Stop {
+        loop {
+            let stop = self.step();
+            if stop != Stop::Stepped {
+                return stop;
+            }
+            if self.breakpoints.contains(&self.computer.instruction_index) {
+                return Stop::Breakpoint(self.computer.instruction_index);
+            }
+        }
+    }
+
+    /// The trace so far, one instruction per line
+    pub fn trace_text(&self) -> String {
+        self.trace.iter().map(|step| format!("{}\n", step)).collect()
+    }
+
+    /// The trace so far as a JSON array with an object per instruction
+    pub fn trace_json(&self) -> String {
+        let steps: Vec<String> = self
+            .trace
+            .iter()
+            .map(|step| {
+                let output = step.output.map_or("null".to_string(), |output| output.to_string());
+                format!(
+                    "{{\"instruction_index\":{},\"opcode\":{},\"operand\":{},\"instruction\":\"{}\",\"before\":{:?},\"after\":{:?},\"output\":{}}}",
+                    step.instruction_index,
+                    step.opcode,
+                    step.operand,
+                    asm::instruction_text(step.opcode, step.operand),
+                    step.before,
+                    step.after,
+                    output
+                )
+            })
+            .collect();
+
+        format!("[{}]", steps.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FaultKind, A, B};
+
+    // The example from the puzzle, which halves A and prints it until it's 0
+    fn example() -> Debugger {
+        Debugger::new(Computer::new([729, 0, 0], vec![0, 1, 5, 4, 3, 0]))
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = example();
+        debugger.add_breakpoint(4);
+
+        assert_eq!(debugger.resume(), Stop::Breakpoint(4));
+        assert_eq!(debugger.computer().registers()[A], 364);
+        assert_eq!(debugger.computer().output(), [4]);
+        assert_eq!(debugger.step(), Stop::Stepped);
+        assert_eq!(debugger.computer().instruction_index(), 0);
+        assert_eq!(debugger.resume(), Stop::Breakpoint(4));
+        assert_eq!(debugger.trace().len(), 5);
+
+        debugger.remove_breakpoint(4);
+        assert_eq!(debugger.resume(), Stop::Halted);
+        assert_eq!(debugger.computer().output(), [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_watch_and_limit() {
+        let mut debugger = example();
+        debugger.watch(A);
+
+        let stop = Stop::Watch {
+            register: A,
+            before: 729,
+            after: 364,
+        };
+        assert_eq!(debugger.resume(), stop);
+
+        debugger.unwatch(A);
+        debugger.watch(B);
+        debugger.set_limit(Some(10));
+        assert_eq!(debugger.resume(), Stop::Limit(10));
+        assert_eq!(debugger.step(), Stop::Limit(10));
+
+        // jnz to itself never ends while A isn't 0
+        let mut debugger = Debugger::new(Computer::new([1, 0, 0], vec![3, 0]));
+        debugger.set_limit(Some(1000));
+        assert_eq!(debugger.resume(), Stop::Limit(1000));
+
+        let mut debugger = Debugger::new(Computer::new([1, 0, 0], vec![5, 4, 5, 7]));
+        let fault = Fault {
+            instruction_index: 2,
+            kind: FaultKind::ReservedCombo,
+        };
+        assert_eq!(debugger.resume(), Stop::Fault(fault));
+        assert_eq!(debugger.trace().len(), 1);
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut debugger = Debugger::new(Computer::new([10, 0, 0], vec![2, 4, 5, 5]));
+
+        assert_eq!(debugger.resume(), Stop::Halted);
+        assert_eq!(
+            debugger.trace_text(),
+            "  0  bst A    A=10 B=0 C=0 -> A=10 B=2 C=0\n  2  out B    A=10 B=2 C=0 -> A=10 B=2 C=0  out 2\n"
+        );
+        assert_eq!(
+            debugger.trace_json(),
+            "[{\"instruction_index\":0,\"opcode\":2,\"operand\":4,\"instruction\":\"bst A\",\"before\":[10, 0, 0],\"after\":[10, 2, 0],\"output\":null},\
+             {\"instruction_index\":2,\"opcode\":5,\"operand\":5,\"instruction\":\"out B\",\"before\":[10, 2, 0],\"after\":[10, 2, 0],\"output\":2}]"
+        );
+    }
+}
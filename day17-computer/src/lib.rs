@@ -0,0 +1,379 @@
+use std::fmt;
+
+use aoc::parse::{self, List, ParseError};
+use aoc::Solution;
+
+pub use register::Register;
+
+pub mod asm;
+pub mod compile;
+pub mod debug;
+mod register;
+pub mod solve;
+
+#[derive(Debug, Clone, Default)]
+pub struct Computer<R = u64> {
+    registers: [R;3],
+    opcodes: Vec<u8>,
+    instruction_index: usize,
+    output: Vec<u8>,
+    step_limit: Option<usize>
+}
+
+// register-indexes
+pub const A: usize = 0;
+pub const B: usize = 1;
+pub const C: usize = 2;
+
+// Opcodes
+const ADV: u8 = 0;
+const BXL: u8 = 1;
+const BST: u8 = 2;
+const JNZ: u8 = 3;
+const BXC: u8 = 4;
+const OUT: u8 = 5;
+const BDV: u8 = 6;
+const CDV: u8 = 7;
+
+/// How a program ended normally, by running off the end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Halt {
+    pub steps: usize
+}
+
+/// Why a program couldn't go on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultKind {
+    /// Combo operand 7
+    ReservedCombo,
+    BadOpcode(u8),
+    /// An operand that isn't a 3-bit number
+    BadOperand(u8),
+    /// The instruction index is on the last number, so there's no operand
+    PcOutOfRange,
+    /// Dividing by a power of 2 that doesn't fit in a register
+    ShiftOverflow,
+    /// The program ran this many instructions without halting
+    StepLimit(usize)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fault {
+    pub instruction_index: usize,
+    pub kind: FaultKind
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Fault at instruction {}: ", self.instruction_index)?;
+        match self.kind {
+            FaultKind::ReservedCombo => write!(f, "combo operand 7 is reserved"),
+            FaultKind::BadOpcode(opcode) => write!(f, "{} isn't an opcode", opcode),
+            FaultKind::BadOperand(operand) => write!(f, "{} isn't an operand", operand),
+            FaultKind::PcOutOfRange => write!(f, "the last number of the program has no operand"),
+            FaultKind::ShiftOverflow => write!(f, "the shift is too wide for the registers"),
+            FaultKind::StepLimit(limit) => write!(f, "still running after {} steps", limit)
+        }
+    }
+}
+
+impl std::error::Error for Fault {}
+
+impl<R: Register> From<&str> for Computer<R> {
+    fn from(input: &str) -> Self {
+        Self::try_parse(input).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<R: Register> Computer<R> {
+    pub fn new(registers: [R;3], opcodes: Vec<u8>) -> Self {
+        Computer {
+            registers,
+            opcodes,
+            ..Self::default()
+        }
+    }
+
+    /// Fault once the program ran `step_limit` instructions, so programs that loop forever stop
+    pub fn with_step_limit(mut self, step_limit: usize) -> Self {
+        self.step_limit = Some(step_limit);
+        self
+    }
+
+    pub fn registers(&self) -> &[R;3] {
+        &self.registers
+    }
+
+    pub fn set_register(&mut self, register: usize, value: R) {
+        self.registers[register] = value;
+    }
+
+    pub fn program(&self) -> &[u8] {
+        &self.opcodes
+    }
+
+    pub fn instruction_index(&self) -> usize {
+        self.instruction_index
+    }
+
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    fn try_parse(input: &str) -> Result<Self, ParseError> {
+        let [register_input, program_input] = parse::exact_sections(input)?;
+        let mut registers: [R;3] = Default::default();
+        registers[A] = register_input.field("Register A")?;
+        registers[B] = register_input.field("Register B")?;
+        registers[C] = register_input.field("Register C")?;
+        let List(opcodes) = program_input.field::<List<u8, ','>>("Program")?;
+
+        Ok(Computer {
+            registers,
+            opcodes,
+            ..Self::default()
+        })
+    }
+
+    fn combo_value(&self, code: u8) -> Result<R, FaultKind> {
+        match code {
+            0..=3 => Ok(R::from_u8(code)),
+            4 => Ok(self.registers[A].clone()),
+            5 => Ok(self.registers[B].clone()),
+            6 => Ok(self.registers[C].clone()),
+            7 => Err(FaultKind::ReservedCombo),
+            _ => Err(FaultKind::BadOperand(code))
+        }
+    }
+
+    // A divided by 2 to the power of the combo operand
+    fn divided_a(&self, operand: u8) -> Result<R, FaultKind> {
+        self.registers[A].divide(&self.combo_value(operand)?).ok_or(FaultKind::ShiftOverflow)
+    }
+
+    fn step(&mut self, opcode: u8, operand: u8) -> Result<usize, FaultKind> {
+        if operand > 7 {
+            return Err(FaultKind::BadOperand(operand));
+        }
+        let literal_value = R::from_u8(operand);
+
+        match opcode {
+            ADV => { // 0
+                self.registers[A] = self.divided_a(operand)?;
+            },
+            BXL => { // 1
+                self.registers[B] = self.registers[B].xor(&literal_value);
+            },
+            BST => { // 2
+                self.registers[B] = R::from_u8(self.combo_value(operand)?.low_bits());
+            },
+            JNZ => { // 3
+                if !self.registers[A].is_zero() {
+                    return Ok(operand as usize)
+                }
+            },
+            BXC => { // 4
+                self.registers[B] = self.registers[B].xor(&self.registers[C]);
+            },
+            OUT => { // 5
+                let value = self.combo_value(operand)?.low_bits();
+                self.output.push(value);
+            },
+            BDV => { // 6
+                self.registers[B] = self.divided_a(operand)?;
+            },
+            CDV => { // 7
+                self.registers[C] = self.divided_a(operand)?;
+            },
+            _ => return Err(FaultKind::BadOpcode(opcode))
+        }
+
+        Ok(self.instruction_index + 2)
+    }
+
+    // The opcode and operand about to run, None once the computer has halted
+    fn current(&self) -> Result<Option<(u8, u8)>, FaultKind> {
+        let Some(&opcode) = self.opcodes.get(self.instruction_index) else {
+            return Ok(None);
+        };
+        let &operand = self.opcodes.get(self.instruction_index + 1).ok_or(FaultKind::PcOutOfRange)?;
+        Ok(Some((opcode, operand)))
+    }
+
+    fn fault(&self, kind: FaultKind) -> Fault {
+        Fault {
+            instruction_index: self.instruction_index,
+            kind
+        }
+    }
+
+    pub fn run(&mut self) -> Result<Halt, Fault> {
+        let mut steps = 0;
+        while let Some((opcode, operand)) = self.current().map_err(|kind| self.fault(kind))? {
+            if let Some(limit) = self.step_limit.filter(|&limit| steps >= limit) {
+                return Err(self.fault(FaultKind::StepLimit(limit)));
+            }
+            self.instruction_index = self.step(opcode, operand).map_err(|kind| self.fault(kind))?;
+            steps += 1;
+        }
+
+        Ok(Halt { steps })
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Chronospatial Computer";
+
+    type Input = Computer;
+    type Part1 = String;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Computer {
+        Computer::from(input)
+    }
+
+    fn part1(computer: &Computer) -> String {
+        let mut part1_computer = computer.clone();
+        part1_computer.run().unwrap_or_else(|fault| panic!("{}", fault));
+
+        part1_computer.output.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(",")
+    }
+
+    fn part2(computer: &Computer) -> u64 {
+        let solutions = solve::find_register_a(&computer.opcodes, &computer.opcodes)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        solutions[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+
+    macro_rules! test_computer {
+        ($registers:expr, $opcodes:expr) => {
+            Computer::<u64> {
+                registers: $registers,
+                opcodes: $opcodes,
+                ..Computer::default()
+            }
+        };
+    }
+
+    #[test]
+    fn test_run_example1() {
+        let mut computer = test_computer!([0, 0, 9], vec![2, 6]);
+
+        computer.step(2, 6).unwrap();
+        assert_eq!(computer.registers, [0, 1, 9]);
+    }
+
+    #[test]
+    fn test_run_example2() {
+        let mut computer = test_computer!([10, 0, 0], vec![5,0,5,1,5,4]);
+
+        computer.run().unwrap();
+
+        assert_eq!(computer.output, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_run_example3() {
+        let mut computer = test_computer!([2024, 0, 0], vec![0,1,5,4,3,0]);
+
+        computer.run().unwrap();
+
+        assert_eq!(computer.output, vec![4,2,5,6,7,7,7,7,3,1,0]);
+        assert_eq!(computer.registers[A], 0);
+    }
+
+    #[test]
+    fn test_run_example4() {
+        let mut computer = test_computer!([0, 29, 0], vec![1,7]);
+
+        computer.run().unwrap();
+
+        assert_eq!(computer.registers[B], 26);
+    }
+
+    #[test]
+    fn test_run_example5() {
+        let mut computer = test_computer!([0, 2024, 43690], vec![4, 0]);
+
+        computer.run().unwrap();
+
+        assert_eq!(computer.registers[B], 44354);
+    }
+
+    #[test]
+    fn test_run_assembled() {
+        let program = asm::assemble("
+            # Print A in octal, lowest digit first
+            loop:
+                bst A
+                out B
+                adv 3
+                jnz loop
+        ").unwrap();
+        let mut computer = test_computer!([0o1234, 0, 0], program);
+
+        computer.run().unwrap();
+
+        assert_eq!(computer.output, vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_faults() {
+        let fault = |registers: [u64;3], opcodes: Vec<u8>| {
+            let mut computer = test_computer!(registers, opcodes).with_step_limit(10);
+            computer.run().map_err(|fault| (fault.instruction_index, fault.kind))
+        };
+
+        assert_eq!(fault([0, 0, 0], vec![5,0,5,7]), Err((2, FaultKind::ReservedCombo)));
+        assert_eq!(fault([0, 0, 0], vec![8,0]), Err((0, FaultKind::BadOpcode(8))));
+        assert_eq!(fault([0, 0, 0], vec![1,9]), Err((0, FaultKind::BadOperand(9))));
+        assert_eq!(fault([0, 0, 0], vec![5,4,5]), Err((2, FaultKind::PcOutOfRange)));
+        assert_eq!(fault([64, 0, 0], vec![0,4]), Err((0, FaultKind::ShiftOverflow)));
+        assert_eq!(fault([1, 0, 0], vec![3,0]), Err((0, FaultKind::StepLimit(10))));
+        assert_eq!(fault([1, 0, 0], vec![3,6]), Ok(Halt { steps: 1 }));
+
+        let mut wide = Computer::<u128>::new([64, 0, 0], vec![0,4]);
+        assert_eq!(wide.run(), Ok(Halt { steps: 1 }));
+        assert_eq!(wide.registers()[A], 0);
+    }
+
+    #[test]
+    fn test_long_program() {
+        let mut opcodes = [1, 0].repeat(200);
+        opcodes.extend([5, 4]);
+        let mut computer = test_computer!([13, 0, 0], opcodes);
+
+        assert_eq!(computer.run(), Ok(Halt { steps: 201 }));
+        assert_eq!(computer.output, vec![5]);
+    }
+
+    #[test]
+    fn test_fuzz_register_widths() {
+        let mut rng = aoc::gen::Rng::new(25);
+        for _ in 0..2000 {
+            let opcodes: Vec<u8> = (0..rng.below(20)).map(|_| rng.below(9) as u8).collect();
+            let registers = [rng.next_u64() >> rng.below(64), rng.below(100) as u64, rng.below(100) as u64];
+
+            let mut narrow = test_computer!(registers, opcodes.clone()).with_step_limit(1000);
+            if narrow.run().is_err() {
+                continue;
+            }
+
+            let mut wide = Computer::<u128>::new(registers.map(u128::from), opcodes.clone());
+            let mut big = Computer::<BigUint>::new(registers.map(BigUint::from), opcodes.clone());
+            assert!(wide.run().is_ok() && big.run().is_ok(), "{:?}", opcodes);
+            assert_eq!(wide.output, narrow.output, "{:?}", opcodes);
+            assert_eq!(big.output, narrow.output, "{:?}", opcodes);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! What the registers hold. The puzzle doesn't say how wide they are, so the computer can use
+//! `u64`, `u128` or `BigUint`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+
+pub trait Register: Clone + Default + PartialEq + fmt::Debug + fmt::Display + FromStr<Err: fmt::Display> {
+    fn from_u8(value: u8) -> Self;
+
+    /// The value modulo 8
+    fn low_bits(&self) -> u8;
+
+    fn is_zero(&self) -> bool;
+
+    fn xor(&self, other: &Self) -> Self;
+
+    /// The value divided by 2 to the power of `shift`, or `None` when that power doesn't fit in a
+    /// register
+    fn divide(&self, shift: &Self) -> Option<Self>;
+}
+
+macro_rules! fixed_width_register {
+    ($type:ty) => {
+        impl Register for $type {
+            fn from_u8(value: u8) -> Self {
+                value as $type
+            }
+
+            fn low_bits(&self) -> u8 {
+                (self % 8) as u8
+            }
+
+            fn is_zero(&self) -> bool {
+                *self == 0
+            }
+
+            fn xor(&self, other: &Self) -> Self {
+                self ^ other
+            }
+
+            fn divide(&self, shift: &Self) -> Option<Self> {
+                (*shift < <$type>::BITS as $type).then(|| self >> shift)
+            }
+        }
+    };
+}
+
+fixed_width_register!(u64);
+fixed_width_register!(u128);
+
+// Never too narrow: a shift past every bit leaves 0
+impl Register for BigUint {
+    fn from_u8(value: u8) -> Self {
+        BigUint::from(value)
+    }
+
+    fn low_bits(&self) -> u8 {
+        self.iter_u32_digits().next().map_or(0, |digit| (digit % 8) as u8)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.bits() == 0
+    }
+
+    fn xor(&self, other: &Self) -> Self {
+        self ^ other
+    }
+
+    fn divide(&self, shift: &Self) -> Option<Self> {
+        let shift = u64::try_from(shift).unwrap_or(u64::MAX);
+        Some(if shift >= self.bits() { BigUint::default() } else { self >> shift })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widths() {
+        assert_eq!(u64::MAX.divide(&63), Some(1));
+        assert_eq!(u64::MAX.divide(&64), None);
+        assert_eq!(u128::from(u64::MAX).divide(&64), Some(0));
+        assert_eq!((u128::MAX - 2).low_bits(), 5);
+
+        let big = BigUint::from(u128::MAX) * 16u8 + 13u8;
+        assert_eq!(big.low_bits(), 5);
+        assert_eq!(big.divide(&BigUint::from(4u8)), Some(BigUint::from(u128::MAX)));
+        assert_eq!(big.divide(&BigUint::from(u128::MAX)), Some(BigUint::default()));
+        assert!(BigUint::default().is_zero());
+        assert_eq!(BigUint::from_u8(6).xor(&BigUint::from_u8(3)), BigUint::from_u8(5));
+    }
+}
@@ -0,0 +1,203 @@
+//! Working backwards from an output to the values of `A` that print it
+
+use std::fmt;
+
+use crate::compile::Compiled;
+use crate::{asm, ADV, BDV, BST, BXC, BXL, CDV, JNZ, OUT};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The program isn't a loop the solver understands, and why
+    Shape(String),
+    /// No value of `A` makes the program print the target
+    NoSolution,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Shape(reason) => write!(f, "The program isn't a loop over A: {}", reason),
+            SolveError::NoSolution => write!(f, "No value of A prints the target output"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// What each pass through a program's loop does with `A`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Shape {
+    /// The instructions before the closing `jnz 0`
+    body: Vec<u8>,
+    /// How many bits `A` loses on every pass
+    shift: u32,
+    /// How many numbers every pass prints
+    outputs: usize,
+}
+
+// The solver needs a loop that ends in `jnz 0` and has no other jumps, shifts `A` right by a
+// fixed amount and sets `B` and `C` before reading them. Then what a pass prints only depends on
+// `A` when the pass starts, and `A` on the last pass only has the bits shifted out of it.
+fn shape(program: &[u8]) -> Result<Shape, SolveError> {
+    let error = |reason: String| Err(SolveError::Shape(reason));
+    if program.len() < 2 || !program.len().is_multiple_of(2) || program.iter().any(|&number| number > 7) {
+        return error("it isn't a list of 3-bit instructions".to_string());
+    }
+    let Some((body, [JNZ, 0])) = program.split_last_chunk::<2>() else {
+        return error("it doesn't end in `jnz 0`".to_string());
+    };
+
+    let mut shift = 0;
+    let mut outputs = 0;
+    let mut written = [true, false, false];
+    for instruction in body.chunks(2) {
+        let (opcode, operand) = (instruction[0], instruction[1]);
+        let text = asm::instruction_text(opcode, operand);
+
+        let mut reads = vec![];
+        if asm::takes_combo(opcode) {
+            match operand {
+                0..=3 => {}
+                7 => return error(format!("`{}` uses the reserved combo operand", text)),
+                register => reads.push(register as usize - 4),
+            }
+        }
+        match opcode {
+            ADV if operand > 3 => return error(format!("`{}` shifts A by a register", text)),
+            ADV => shift += operand as u32,
+            BXL => reads.push(1),
+            BXC => reads.extend([1, 2]),
+            OUT => outputs += 1,
+            JNZ => return error(format!("`{}` jumps from the middle of the loop", text)),
+            _ => {}
+        }
+
+        if let Some(&register) = reads.iter().find(|&&register| !written[register]) {
+            let name = ["A", "B", "C"][register];
+            return error(format!("`{}` reads {} before the loop sets it", text, name));
+        }
+        match opcode {
+            BXL | BST | BXC | BDV => written[1] = true,
+            CDV => written[2] = true,
+            _ => {}
+        }
+    }
+
+    if shift == 0 {
+        return error("A never gets smaller, so the loop doesn't end".to_string());
+    }
+    if outputs == 0 {
+        return error("it never prints anything".to_string());
+    }
+
+    Ok(Shape {
+        body: body.to_vec(),
+        shift,
+        outputs,
+    })
+}
+
+/// Every value of `A` that makes `program` print `target_output`, smallest first. Works for
+/// programs that loop over `A` a few bits at a time, however many bits and outputs a pass takes.
+pub fn find_register_a(program: &[u8], target_output: &[u8]) -> Result<Vec<u64>, SolveError> {
+    let shape = shape(program)?;
+    if target_output.is_empty() || !target_output.len().is_multiple_of(shape.outputs) {
+        return Err(SolveError::NoSolution);
+    }
+    let passes = target_output.len() / shape.outputs;
+    let body = Compiled::new(&shape.body).map_err(SolveError::Shape)?;
+
+    // Going from the last pass to the first, `A` gets `shift` more bits at the bottom every time.
+    // Only `A` on the first pass can be 0, otherwise the `jnz` before would have stopped.
+    let mut solutions = vec![];
+    let mut candidates = vec![(passes, 0u64)];
+    while let Some((pass, after)) = candidates.pop() {
+        if pass == 0 {
+            solutions.push(after);
+            continue;
+        }
+        if after.leading_zeros() < shape.shift {
+            continue;
+        }
+
+        let expected = &target_output[(pass - 1) * shape.outputs..pass * shape.outputs];
+        for low_bits in 0..1 << shape.shift {
+            let a = after << shape.shift | low_bits;
+            if a == 0 && pass > 1 {
+                continue;
+            }
+
+            if body.prints([a, 0, 0], expected) {
+                candidates.push((pass - 1, a));
+            }
+        }
+    }
+
+    if solutions.is_empty() {
+        return Err(SolveError::NoSolution);
+    }
+    solutions.sort_unstable();
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assemble;
+    use crate::Computer;
+
+    fn run(program: &[u8], a: u64) -> Vec<u8> {
+        let mut computer = Computer::new([a, 0, 0], program.to_vec());
+        computer.run().unwrap();
+        computer.output
+    }
+
+    #[test]
+    fn test_quine() {
+        let program = [0, 3, 5, 4, 3, 0];
+
+        let solutions = find_register_a(&program, &program).unwrap();
+        assert_eq!(solutions[0], 117440);
+        for a in solutions {
+            assert_eq!(run(&program, a), program);
+        }
+    }
+
+    #[test]
+    fn test_other_loop_shapes() {
+        // Two bits and two outputs per pass
+        let program = assemble(
+            "
+            l0: bst A
+                out B
+                bxl 1
+                out B
+                adv 2
+                jnz l0
+            ",
+        )
+        .unwrap();
+
+        let target = [6, 7, 5, 4, 3, 2];
+        let solutions = find_register_a(&program, &target).unwrap();
+        assert_eq!(solutions, [0b110110]);
+        assert_eq!(run(&program, solutions[0]), target);
+
+        assert_eq!(find_register_a(&program, &[2, 2]), Err(SolveError::NoSolution));
+        assert_eq!(find_register_a(&program, &[2, 3, 1]), Err(SolveError::NoSolution));
+    }
+
+    #[test]
+    fn test_unsupported_shapes() {
+        let reason = |source: &str| match find_register_a(&assemble(source).unwrap(), &[0]) {
+            Err(SolveError::Shape(reason)) => reason,
+            other => panic!("{:?}", other),
+        };
+
+        assert_eq!(reason("out A"), "it doesn't end in `jnz 0`");
+        assert_eq!(reason("out B\nadv 1\njnz 0"), "`out B` reads B before the loop sets it");
+        assert_eq!(reason("adv B\nout A\njnz 0"), "`adv B` shifts A by a register");
+        assert_eq!(reason("out A\njnz 0"), "A never gets smaller, so the loop doesn't end");
+        assert_eq!(reason("adv 1\njnz 0"), "it never prints anything");
+    }
+}
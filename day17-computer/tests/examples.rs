@@ -0,0 +1 @@
+aoc::example_tests!(day17_computer::Day17);
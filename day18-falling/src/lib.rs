@@ -0,0 +1,130 @@
+use std::fmt::Display;
+
+use aoc::search::bfs;
+use aoc::{Map, Params, Pos, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Empty,
+    Wall,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let c = match self {
+            Tile::Empty => '.',
+            Tile::Wall => '#',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct MemorySpace {
+    incoming_bytes: Vec<Pos>,
+    map: Map<Tile>,
+    size: usize,
+    // How many bytes fall before part 1 looks for a path
+    bytes: usize,
+}
+
+impl MemorySpace {
+    fn new(input: &str, size: usize, bytes: usize) -> Self {
+        let incoming_bytes = input
+            .lines()
+            .filter_map(|line| {
+                let mut nums = line.split(",").filter_map(|n| n.parse().ok());
+
+                // Our map implementation uses (y, x) coordinates
+                let y = nums.next()?;
+                let x = nums.next()?;
+
+                Some(Pos::new(x, y))
+            })
+            .collect();
+
+        Self {
+            incoming_bytes,
+            size,
+            bytes,
+            ..Default::default()
+        }
+    }
+
+    fn drop_bytes(&mut self, count: usize) {
+        self.map = Map::new(self.size, self.size, Tile::Empty);
+
+        for &byte in &self.incoming_bytes[..count] {
+            self.map.set_point(byte, Tile::Wall);
+        }
+    }
+
+    fn exit(&self) -> Pos {
+        Pos::new(self.size as isize - 1, self.size as isize - 1)
+    }
+
+    fn shortest_path(&self, end_point: Pos) -> Option<u32> {
+        bfs(
+            [Pos::ZERO],
+            |&position| {
+                self.map
+                    .cardinal_neighbours(position)
+                    .filter(|(_, tile)| matches!(tile, Tile::Empty))
+                    .map(|(neighbor, _)| neighbor)
+                    .collect::<Vec<Pos>>()
+            },
+            |&position| position == end_point,
+        )
+        .goal_cost()
+    }
+}
+
+// The real memory space and how many bytes fall in it for part 1, the example has fewer of both
+const MAP_SIZE: usize = 71;
+const BYTES: usize = 1024;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "RAM Run";
+
+    type Input = MemorySpace;
+    type Part1 = u32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> MemorySpace {
+        Self::parse_with(input, &Params::default())
+    }
+
+    // The sidecar of the example sets `size` and `bytes`
+    fn parse_with(input: &str, params: &Params) -> MemorySpace {
+        let setting = |name, default| params.get_or(name, default).unwrap_or_else(|error| panic!("{}", error));
+
+        MemorySpace::new(input, setting("size", MAP_SIZE), setting("bytes", BYTES))
+    }
+
+    fn part1(memory_space: &MemorySpace) -> u32 {
+        let mut memory_space = memory_space.clone();
+        memory_space.drop_bytes(memory_space.bytes);
+
+        memory_space.shortest_path(memory_space.exit()).unwrap()
+    }
+
+    fn part2(memory_space: &MemorySpace) -> String {
+        let mut memory_space = memory_space.clone();
+        memory_space.drop_bytes(memory_space.bytes);
+
+        let mut idx = memory_space.bytes;
+        while memory_space.shortest_path(memory_space.exit()).is_some() {
+            let byte = memory_space.incoming_bytes[idx];
+            memory_space.map.set_point(byte, Tile::Wall);
+            idx += 1;
+        }
+
+        // Swap back to the puzzle's x,y order
+        let byte = memory_space.incoming_bytes[idx - 1];
+        format!("{},{}", byte.y, byte.x)
+    }
+}
@@ -0,0 +1 @@
+aoc::example_tests!(day18_falling::Day18);
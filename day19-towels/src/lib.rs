@@ -0,0 +1,75 @@
+use aoc::parse;
+use aoc::Solution;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct Puzzle {
+    options: Vec<(String, usize)>,
+    towels: Vec<String>,
+}
+
+impl From<&str> for Puzzle {
+    fn from(input: &str) -> Self {
+        let [options_data, towels_data] = parse::exact_sections(input).unwrap_or_else(|error| panic!("{}", error));
+
+        Puzzle {
+            options: options_data.text.split(", ").map(|option| (option.to_string(), option.len())).collect(),
+            towels: towels_data.text.lines().map(String::from).collect(),
+        }
+    }
+}
+
+impl Puzzle {
+    fn makeable_options(&self) -> Vec<u64> {
+        let mut makeable_cache: HashMap<&[u8], u64> = HashMap::new();
+        self.towels
+            .iter()
+            .map(|towel| self.makeable_ways(towel.as_bytes(), &mut makeable_cache))
+            .collect()
+    }
+
+    fn makeable_ways<'a>(&self, towel: &'a [u8], cache: &mut HashMap<&'a [u8], u64>) -> u64 {
+        if let Some(&ways) = cache.get(towel) {
+            return ways;
+        }
+
+        if towel.is_empty() {
+            return 1;
+        }
+
+        let mut result = 0;
+
+        for (option, len) in &self.options {
+            if towel.starts_with(option.as_bytes()) {
+                let remaining = &towel[*len..];
+                result += self.makeable_ways(remaining, cache);
+            }
+        }
+
+        cache.insert(towel, result);
+        result
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Linen Layout";
+
+    type Input = Puzzle;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Puzzle {
+        input.into()
+    }
+
+    fn part1(puzzle: &Puzzle) -> usize {
+        puzzle.makeable_options().iter().filter(|&&count| count > 0).count()
+    }
+
+    fn part2(puzzle: &Puzzle) -> u64 {
+        puzzle.makeable_options().iter().sum::<u64>()
+    }
+}
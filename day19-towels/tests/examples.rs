@@ -0,0 +1 @@
+aoc::example_tests!(day19_towels::Day19);
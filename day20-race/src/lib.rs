@@ -0,0 +1,135 @@
+use std::fmt;
+
+use aoc::search::distance_field;
+use aoc::{Map, Params, Pos, Solution};
+
+enum Tile {
+    Racetrack,
+    Wall,
+    Start,
+    Finish,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Tile::Wall),
+            '.' => Ok(Tile::Racetrack),
+            'S' => Ok(Tile::Start),
+            'E' => Ok(Tile::Finish),
+            _ => Err(c),
+        }
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Tile::Racetrack => '.',
+            Tile::Wall => '#',
+            Tile::Start => 'S',
+            Tile::Finish => 'E',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+pub struct Track {
+    map: Map<Tile>,
+    finish: Pos,
+    // How much time cheats need to save to count, in part 1 and part 2
+    thresholds: (usize, usize),
+}
+
+// The real puzzle only counts cheats that save at least 100 picoseconds
+const MIN_SAVING: usize = 100;
+
+impl Track {
+    fn new(s: &str, thresholds: (usize, usize)) -> Self {
+        let map = Map::try_parse(s).unwrap_or_else(|error| panic!("{}", error));
+        let finish = map
+            .iter()
+            .find(|(_, tile)| matches!(tile, Tile::Finish))
+            .map(|(point, _)| point)
+            .unwrap_or_default();
+
+        Self { map, finish, thresholds }
+    }
+
+    // Time left to the finish from every position on the track
+    fn times_per_positon(&self) -> Map<Option<u32>> {
+        distance_field(&self.map, [self.finish], |_, tile| !matches!(tile, Tile::Wall))
+    }
+
+    fn completion_times(&self, cheats: usize) -> Vec<usize> {
+        let mut times = vec![];
+        let times_per_position = self.times_per_positon();
+
+        for (point, tile) in self.map.iter() {
+            if matches!(tile, Tile::Racetrack | Tile::Start) {
+                let Some(&Some(normal_time)) = times_per_position.at_point(point) else {
+                    continue;
+                };
+                let normal_time = normal_time as usize;
+
+                // Draw a cheat-size diamond around this point and see if there's time to save
+                for cheat_end in point.diamond(cheats) {
+                    if let Some(&Some(cheat_time)) = times_per_position.at_point(cheat_end) {
+                        let new_time = cheat_time as usize + point.manhattan(cheat_end);
+                        if normal_time > new_time {
+                            times.push(normal_time - new_time);
+                        }
+                    }
+                }
+            }
+        }
+
+        times
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Race Condition";
+
+    type Input = Track;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Track {
+        Self::parse_with(input, &Params::default())
+    }
+
+    // The example counts every cheat in part 1 and those that save 50 in part 2, its sidecar sets
+    // `min_saving1` and `min_saving2`
+    fn parse_with(input: &str, params: &Params) -> Track {
+        let setting = |name, default| params.get_or(name, default).unwrap_or_else(|error| panic!("{}", error));
+
+        Track::new(input, (setting("min_saving1", MIN_SAVING), setting("min_saving2", MIN_SAVING)))
+    }
+
+    fn part1(track: &Track) -> usize {
+        track.completion_times(2).iter().filter(|&t| *t >= track.thresholds.0).count()
+    }
+
+    fn part2(track: &Track) -> usize {
+        track.completion_times(20).iter().filter(|&t| *t >= track.thresholds.1).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completion_times() {
+        let track = Day20::parse(include_str!("../example.txt").trim_end());
+
+        assert_eq!(track.completion_times(2).iter().filter(|&t| *t > 0).count(), 44);
+        assert_eq!(track.completion_times(20).iter().filter(|&t| *t >= 50).count(), 285);
+    }
+}
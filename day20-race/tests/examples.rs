@@ -0,0 +1 @@
+aoc::example_tests!(day20_race::Day20);
@@ -0,0 +1 @@
+aoc::example_tests!(day21_keypads::Day21);
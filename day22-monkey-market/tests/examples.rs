@@ -0,0 +1 @@
+aoc::example_tests!(day22_monkey_market::Day22);
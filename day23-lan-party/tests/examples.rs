@@ -0,0 +1 @@
+aoc::example_tests!(day23_lan_party::Day23);
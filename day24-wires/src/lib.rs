@@ -0,0 +1,121 @@
+use aoc::parse::{self, Field};
+use aoc::{Solution, Unsolved};
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug,Clone,Copy)]
+enum Op {
+    Xor,
+    Or,
+    And
+}
+
+impl From<&str> for Op {
+    fn from(input: &str) -> Self {
+        match input {
+            "AND" => Self::And,
+            "OR" => Self::Or,
+            "XOR" => Self::Xor,
+            _ => panic!("Invalid operation")
+        }
+    }
+}
+
+type Operation = (String, Op, String, String);
+
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    gates: HashMap<String, bool>,
+    operations: Vec<Operation>
+}
+
+impl From<&str> for Puzzle {
+    fn from(input: &str) -> Self {
+        let [gate_data, operations_data] = parse::exact_sections(input).unwrap_or_else(|error| panic!("{}", error));
+        let gates = gate_data
+            .records::<Field<u8>>()
+            .unwrap_or_else(|error| panic!("{}", error))
+            .into_iter()
+            .map(|Field { key, value }| (key, value == 1))
+            .collect();
+
+        let operations = operations_data.text.lines().map(|line| {
+            let mut parts = line.split_whitespace();
+            let input1 = parts.next().unwrap();
+            let operation = parts.next().unwrap().into();
+            let input2 = parts.next().unwrap();
+            parts.next(); // Skip "->"
+            let output = parts.next().unwrap();
+
+            (input1.to_string(), operation, input2.to_string(), output.to_string())
+        }).collect();
+
+        Self {
+            gates,
+            operations
+        }
+    }
+}
+
+impl Puzzle {
+    fn solve(&mut self, operation: &Operation) -> Option<bool> {
+        let (input1, operation, input2, output) = operation;
+
+        let &value1 = self.gates.get(input1)?;
+        let &value2 = self.gates.get(input2)?;
+
+        let result = match operation {
+            Op::And => value1 & value2,
+            Op::Or => value1 | value2,
+            Op::Xor => value1 ^ value2
+        };
+
+        self.gates.insert(output.clone(), result);
+
+        Some(result)
+    }
+
+    fn solve_all(&mut self) {
+        let mut operation_queue = VecDeque::from(self.operations.clone());
+
+        while let Some(operation) = operation_queue.pop_front() {
+            if self.solve(&operation).is_none() {
+                operation_queue.push_back(operation);
+            }
+        }
+    }
+
+    fn score(&self) -> usize {
+        let mut z_gates: Vec<(&String, &bool)> = self.gates.iter().filter(|(gate, _)| gate.starts_with("z")).collect();
+        z_gates.sort_unstable_by_key(|&(gate, _)| gate.clone() );
+
+        z_gates.iter().enumerate().fold(0, |acc, (i, (_, &value))| {
+            acc | (value as usize) << i
+        })
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "Crossed Wires";
+
+    type Input = Puzzle;
+    type Part1 = usize;
+    type Part2 = Unsolved;
+
+    fn parse(input: &str) -> Puzzle {
+        input.into()
+    }
+
+    fn part1(puzzle: &Puzzle) -> usize {
+        let mut puzzle = puzzle.clone();
+
+        puzzle.solve_all();
+        puzzle.score()
+    }
+
+    fn part2(_: &Puzzle) -> Unsolved {
+        Unsolved
+    }
+}
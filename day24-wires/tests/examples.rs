@@ -0,0 +1 @@
+aoc::example_tests!(day24_wires::Day24);